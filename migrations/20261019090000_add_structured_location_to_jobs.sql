@@ -0,0 +1,14 @@
+-- Add migration script here
+CREATE EXTENSION IF NOT EXISTS cube;
+CREATE EXTENSION IF NOT EXISTS earthdistance;
+
+ALTER TABLE jobs
+    ADD COLUMN country TEXT,
+    ADD COLUMN region TEXT,
+    ADD COLUMN city TEXT,
+    ADD COLUMN postal_code TEXT,
+    ADD COLUMN latitude DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    ADD COLUMN longitude DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180),
+    ADD CONSTRAINT jobs_coordinates_check CHECK ((latitude IS NULL) = (longitude IS NULL));
+
+CREATE INDEX jobs_earth_location_idx ON jobs USING gist (ll_to_earth(latitude, longitude));
@@ -0,0 +1,69 @@
+-- Add migration script here
+ALTER TABLE users
+    DROP CONSTRAINT users_role_check,
+    ADD CONSTRAINT users_role_check CHECK (role IN ('job_seeker', 'employer', 'admin'));
+
+CREATE TABLE categories (
+    id SERIAL PRIMARY KEY,
+    parent_id INTEGER REFERENCES categories(id) ON DELETE RESTRICT,
+    name TEXT NOT NULL,
+    slug TEXT UNIQUE NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX categories_parent_id_idx ON categories (parent_id);
+
+-- Map the free-text categories onto rows. Spellings that only differ in case or
+-- spacing ("IT", "it") share a row named after the most common spelling; names
+-- whose slug comes out empty or clashes with another name's ("C++", "C#") get an
+-- md5 suffix instead of being merged.
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+CREATE TEMPORARY TABLE category_keys AS
+WITH spellings AS (
+    SELECT
+        lower(regexp_replace(COALESCE(NULLIF(trim(category), ''), 'Uncategorized'), '\s+', ' ', 'g')) AS key,
+        COALESCE(NULLIF(trim(category), ''), 'Uncategorized') AS name,
+        count(*) AS uses
+    FROM jobs
+    GROUP BY 1, 2
+), keys AS (
+    SELECT DISTINCT ON (key)
+        key,
+        name,
+        trim(BOTH '-' FROM regexp_replace(unaccent(key), '[^a-z0-9]+', '-', 'g')) AS base
+    FROM spellings
+    ORDER BY key, uses DESC, name
+)
+SELECT
+    key,
+    name,
+    CASE
+        WHEN base = '' OR count(*) OVER (PARTITION BY base) > 1
+        THEN concat_ws('-', NULLIF(base, ''), left(md5(key), 8))
+        ELSE base
+    END AS slug
+FROM keys;
+
+INSERT INTO categories (name, slug)
+SELECT name, slug
+FROM category_keys
+ORDER BY slug;
+
+ALTER TABLE jobs ADD COLUMN category_id INTEGER REFERENCES categories(id) ON DELETE RESTRICT;
+
+UPDATE jobs
+SET category_id = categories.id
+FROM category_keys
+JOIN categories ON categories.slug = category_keys.slug
+WHERE category_keys.key =
+    lower(regexp_replace(COALESCE(NULLIF(trim(jobs.category), ''), 'Uncategorized'), '\s+', ' ', 'g'));
+
+DROP TABLE category_keys;
+
+ALTER TABLE jobs
+    ALTER COLUMN category_id SET NOT NULL,
+    DROP COLUMN category;
+
+CREATE INDEX jobs_category_id_idx ON jobs (category_id);
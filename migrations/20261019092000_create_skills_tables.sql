@@ -0,0 +1,29 @@
+-- Add migration script here
+CREATE TABLE skills (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX skills_lower_name_idx ON skills (lower(name));
+
+-- Aliases are stored lowercased so lookups can compare against lower(input).
+CREATE TABLE skill_aliases (
+    alias TEXT PRIMARY KEY,
+    skill_id INTEGER NOT NULL REFERENCES skills(id) ON DELETE CASCADE
+);
+
+CREATE TABLE job_skills (
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    skill_id INTEGER NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+    required BOOLEAN NOT NULL DEFAULT TRUE,
+    PRIMARY KEY (job_id, skill_id)
+);
+
+CREATE INDEX job_skills_skill_id_idx ON job_skills (skill_id);
+
+CREATE TABLE user_skills (
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    skill_id INTEGER NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, skill_id)
+);
@@ -0,0 +1,47 @@
+-- Add migration script here
+CREATE TABLE companies (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    logo_url TEXT,
+    website TEXT,
+    description TEXT NOT NULL DEFAULT '',
+    size TEXT CHECK (size IN ('1-10', '11-50', '51-200', '201-500', '501-1000', '1000+')),
+    industry TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE company_members (
+    company_id INTEGER NOT NULL REFERENCES companies(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL CHECK (role IN ('owner', 'admin', 'recruiter')),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (company_id, user_id)
+);
+
+CREATE INDEX company_members_user_id_idx ON company_members (user_id);
+
+-- Every existing employer (and anyone who already posted jobs) becomes the
+-- owner of a company named after them, and their jobs move into it.
+ALTER TABLE companies ADD COLUMN legacy_owner_id INTEGER;
+
+INSERT INTO companies (name, legacy_owner_id)
+SELECT username, id
+FROM users
+WHERE role = 'employer' OR id IN (SELECT employer_id FROM jobs);
+
+INSERT INTO company_members (company_id, user_id, role)
+SELECT id, legacy_owner_id, 'owner'
+FROM companies;
+
+ALTER TABLE jobs ADD COLUMN company_id INTEGER REFERENCES companies(id) ON DELETE RESTRICT;
+
+UPDATE jobs
+SET company_id = companies.id
+FROM companies
+WHERE companies.legacy_owner_id = jobs.employer_id;
+
+ALTER TABLE jobs ALTER COLUMN company_id SET NOT NULL;
+ALTER TABLE companies DROP COLUMN legacy_owner_id;
+
+CREATE INDEX jobs_company_id_idx ON jobs (company_id);
@@ -0,0 +1,10 @@
+-- Add migration script here
+-- One row per visitor, job, event kind and day: re-inserting the same
+-- combination is a no-op, which is what de-duplicates repeat views.
+CREATE TABLE job_events (
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('view', 'apply_click')),
+    visitor_key TEXT NOT NULL,
+    occurred_on DATE NOT NULL,
+    PRIMARY KEY (job_id, kind, occurred_on, visitor_key)
+);
@@ -0,0 +1,24 @@
+-- Add migration script here
+CREATE TABLE saved_searches (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    q TEXT,
+    category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL,
+    city TEXT,
+    country TEXT,
+    min_salary NUMERIC(10, 2),
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    radius_km DOUBLE PRECISION,
+    frequency TEXT NOT NULL CHECK (frequency IN ('instant', 'daily', 'weekly')) DEFAULT 'daily',
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    unsubscribe_token TEXT UNIQUE NOT NULL DEFAULT gen_random_uuid()::text,
+    last_run_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((latitude IS NULL) = (longitude IS NULL))
+);
+
+CREATE INDEX saved_searches_user_id_idx ON saved_searches (user_id);
+CREATE INDEX saved_searches_due_idx ON saved_searches (frequency, last_run_at) WHERE active;
@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE jobs ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE applications ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
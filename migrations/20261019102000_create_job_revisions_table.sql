@@ -0,0 +1,34 @@
+-- Add migration script here
+CREATE TABLE job_revisions (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    location TEXT NOT NULL,
+    country TEXT,
+    region TEXT,
+    city TEXT,
+    postal_code TEXT,
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    salary NUMERIC(10, 2) NOT NULL,
+    category_id INTEGER NOT NULL,
+    expires_at TIMESTAMP,
+    author_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (job_id, version)
+);
+
+-- Existing postings start their history at their current state.
+INSERT INTO job_revisions (job_id, version, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, expires_at, author_id, created_at)
+SELECT id, version, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, expires_at, employer_id, updated_at
+FROM jobs;
+
+ALTER TABLE applications
+    ADD COLUMN job_revision_id INTEGER REFERENCES job_revisions(id) ON DELETE SET NULL;
+
+UPDATE applications a
+SET job_revision_id = r.id
+FROM job_revisions r
+WHERE r.job_id = a.job_id;
@@ -0,0 +1,7 @@
+-- Add migration script here
+ALTER TABLE jobs ADD COLUMN deleted_at TIMESTAMP;
+
+ALTER TABLE users ADD COLUMN deleted_at TIMESTAMP;
+
+CREATE INDEX jobs_deleted_at_idx ON jobs (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX users_deleted_at_idx ON users (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,28 @@
+-- Add migration script here
+CREATE TABLE job_questions (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    prompt TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('yes_no', 'multiple_choice', 'numeric', 'short_text')),
+    required BOOLEAN NOT NULL DEFAULT TRUE,
+    options TEXT[] NOT NULL DEFAULT '{}',
+    min_value DOUBLE PRECISION,
+    max_value DOUBLE PRECISION,
+    max_length INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX job_questions_job_id_idx ON job_questions (job_id, position);
+
+-- Answers keep a copy of the prompt so they stay readable after the
+-- employer edits or removes the question.
+CREATE TABLE application_answers (
+    id SERIAL PRIMARY KEY,
+    application_id INTEGER NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    question_id INTEGER REFERENCES job_questions(id) ON DELETE SET NULL,
+    prompt TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    answer TEXT NOT NULL,
+    UNIQUE (application_id, question_id)
+);
@@ -0,0 +1,30 @@
+-- Add migration script here
+CREATE TABLE knockout_rules (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    question_id INTEGER NOT NULL REFERENCES job_questions(id) ON DELETE CASCADE,
+    operator TEXT NOT NULL CHECK (operator IN ('eq', 'neq', 'gt', 'gte', 'lt', 'lte')),
+    value TEXT NOT NULL,
+    reject_delay_minutes INTEGER NOT NULL DEFAULT 0 CHECK (reject_delay_minutes >= 0),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX knockout_rules_job_id_idx ON knockout_rules (job_id);
+
+-- Evaluations describe the rule in words so they stay meaningful after the
+-- rule itself is removed.
+CREATE TABLE rule_evaluations (
+    id SERIAL PRIMARY KEY,
+    application_id INTEGER NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    rule_id INTEGER REFERENCES knockout_rules(id) ON DELETE SET NULL,
+    description TEXT NOT NULL,
+    answer TEXT,
+    passed BOOLEAN NOT NULL,
+    evaluated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX rule_evaluations_application_id_idx ON rule_evaluations (application_id);
+
+ALTER TABLE applications ADD COLUMN auto_reject_at TIMESTAMP;
+
+CREATE INDEX applications_auto_reject_at_idx ON applications (auto_reject_at) WHERE auto_reject_at IS NOT NULL;
@@ -0,0 +1,6 @@
+-- Add migration script here
+ALTER TABLE jobs ADD COLUMN external_ref TEXT;
+
+-- Soft-deleted jobs keep their reference so restoring them never collides.
+CREATE UNIQUE INDEX jobs_company_external_ref_key ON jobs (company_id, external_ref)
+    WHERE external_ref IS NOT NULL;
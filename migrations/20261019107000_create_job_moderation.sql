@@ -0,0 +1,22 @@
+-- Add migration script here
+ALTER TABLE companies ADD COLUMN verified BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Postings that predate moderation stay live; new ones wait for review.
+ALTER TABLE jobs
+    ADD COLUMN moderation_status TEXT NOT NULL DEFAULT 'approved'
+        CHECK (moderation_status IN ('pending', 'approved', 'rejected', 'changes_requested'));
+ALTER TABLE jobs ALTER COLUMN moderation_status SET DEFAULT 'pending';
+
+CREATE INDEX jobs_moderation_status_idx ON jobs (moderation_status) WHERE moderation_status <> 'approved';
+
+CREATE TABLE moderation_events (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    action TEXT NOT NULL CHECK (action IN ('submitted', 'approved', 'rejected', 'changes_requested')),
+    reason TEXT,
+    flags TEXT[] NOT NULL DEFAULT '{}',
+    moderator_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX moderation_events_job_id_idx ON moderation_events (job_id, created_at);
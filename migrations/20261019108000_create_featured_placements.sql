@@ -0,0 +1,20 @@
+-- Add migration script here
+CREATE TABLE featured_placements (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    starts_at TIMESTAMP NOT NULL,
+    ends_at TIMESTAMP NOT NULL,
+    -- Higher weights take the earlier featured slots.
+    weight INTEGER NOT NULL DEFAULT 1 CHECK (weight BETWEEN 1 AND 100),
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX featured_placements_job_id_idx ON featured_placements (job_id);
+CREATE INDEX featured_placements_window_idx ON featured_placements (starts_at, ends_at);
+
+-- Featured slots are counted apart from organic views and clicks.
+ALTER TABLE job_events DROP CONSTRAINT job_events_kind_check;
+ALTER TABLE job_events ADD CONSTRAINT job_events_kind_check
+    CHECK (kind IN ('view', 'apply_click', 'featured_impression', 'featured_click'));
@@ -0,0 +1,62 @@
+-- Add migration script here
+-- The text search configuration for a locale such as 'de' or 'pt-br', by its
+-- primary language; languages without a stemmer use 'simple'.
+CREATE FUNCTION locale_ts_config(locale TEXT) RETURNS regconfig
+LANGUAGE SQL IMMUTABLE PARALLEL SAFE AS $$
+    SELECT CASE split_part(lower(locale), '-', 1)
+        WHEN 'ar' THEN 'arabic'::regconfig
+        WHEN 'ca' THEN 'catalan'::regconfig
+        WHEN 'da' THEN 'danish'::regconfig
+        WHEN 'de' THEN 'german'::regconfig
+        WHEN 'el' THEN 'greek'::regconfig
+        WHEN 'en' THEN 'english'::regconfig
+        WHEN 'es' THEN 'spanish'::regconfig
+        WHEN 'eu' THEN 'basque'::regconfig
+        WHEN 'fi' THEN 'finnish'::regconfig
+        WHEN 'fr' THEN 'french'::regconfig
+        WHEN 'ga' THEN 'irish'::regconfig
+        WHEN 'hi' THEN 'hindi'::regconfig
+        WHEN 'hu' THEN 'hungarian'::regconfig
+        WHEN 'hy' THEN 'armenian'::regconfig
+        WHEN 'id' THEN 'indonesian'::regconfig
+        WHEN 'it' THEN 'italian'::regconfig
+        WHEN 'lt' THEN 'lithuanian'::regconfig
+        WHEN 'nb' THEN 'norwegian'::regconfig
+        WHEN 'ne' THEN 'nepali'::regconfig
+        WHEN 'nl' THEN 'dutch'::regconfig
+        WHEN 'nn' THEN 'norwegian'::regconfig
+        WHEN 'no' THEN 'norwegian'::regconfig
+        WHEN 'pt' THEN 'portuguese'::regconfig
+        WHEN 'ro' THEN 'romanian'::regconfig
+        WHEN 'ru' THEN 'russian'::regconfig
+        WHEN 'sr' THEN 'serbian'::regconfig
+        WHEN 'sv' THEN 'swedish'::regconfig
+        WHEN 'ta' THEN 'tamil'::regconfig
+        WHEN 'tr' THEN 'turkish'::regconfig
+        WHEN 'yi' THEN 'yiddish'::regconfig
+        ELSE 'simple'::regconfig
+    END
+$$;
+
+-- The language the posting's own title and description are written in.
+ALTER TABLE jobs ADD COLUMN language TEXT NOT NULL DEFAULT 'en';
+ALTER TABLE jobs ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector(locale_ts_config(language), title), 'A')
+    || setweight(to_tsvector(locale_ts_config(language), description), 'B')
+) STORED;
+CREATE INDEX jobs_search_vector_idx ON jobs USING GIN (search_vector);
+
+CREATE TABLE job_translations (
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    locale TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector(locale_ts_config(locale), title), 'A')
+        || setweight(to_tsvector(locale_ts_config(locale), description), 'B')
+    ) STORED,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (job_id, locale)
+);
+CREATE INDEX job_translations_search_vector_idx ON job_translations USING GIN (search_vector);
@@ -0,0 +1,30 @@
+-- Add migration script here
+-- Drafts are private to the company until published.
+ALTER TABLE jobs DROP CONSTRAINT jobs_status_check;
+ALTER TABLE jobs ADD CONSTRAINT jobs_status_check CHECK (status IN ('draft', 'open', 'closed'));
+
+-- Titles, descriptions and locations may contain {{placeholders}} that are
+-- filled in when a draft is created from the template.
+CREATE TABLE job_templates (
+    id SERIAL PRIMARY KEY,
+    company_id INTEGER NOT NULL REFERENCES companies(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    location TEXT NOT NULL,
+    salary NUMERIC(10, 2) NOT NULL DEFAULT 0.00,
+    category_id INTEGER NOT NULL REFERENCES categories(id) ON DELETE RESTRICT,
+    language TEXT NOT NULL DEFAULT 'en',
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (company_id, name)
+);
+
+-- Where a posting was copied from, kept for reporting.
+ALTER TABLE jobs
+    ADD COLUMN source_job_id INTEGER REFERENCES jobs(id) ON DELETE SET NULL,
+    ADD COLUMN template_id INTEGER REFERENCES job_templates(id) ON DELETE SET NULL;
+
+CREATE INDEX jobs_source_job_id_idx ON jobs (source_job_id) WHERE source_job_id IS NOT NULL;
+CREATE INDEX jobs_template_id_idx ON jobs (template_id) WHERE template_id IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Add migration script here
+-- Drafts with a publish_at are opened by the scheduler once it has passed.
+ALTER TABLE jobs
+    ADD COLUMN publish_at TIMESTAMP,
+    ADD COLUMN expiry_reminded_at TIMESTAMP,
+    -- Set when the expiry reminder goes out; lets the employer extend the
+    -- posting from the email without logging in.
+    ADD COLUMN extend_token TEXT UNIQUE;
+
+CREATE INDEX jobs_publish_at_idx ON jobs (publish_at) WHERE status = 'draft';
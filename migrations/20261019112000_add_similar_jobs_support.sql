@@ -0,0 +1,19 @@
+-- Add migration script here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX jobs_title_trgm_idx ON jobs USING GIN (title gin_trgm_ops);
+
+-- Bumped by every statement that changes jobs, so caches derived from many
+-- postings, on any server instance, can tell when they are stale.
+CREATE SEQUENCE job_changes_seq;
+
+CREATE FUNCTION bump_job_changes() RETURNS trigger AS $$
+BEGIN
+    PERFORM nextval('job_changes_seq');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER jobs_bump_changes
+    AFTER INSERT OR UPDATE OR DELETE ON jobs
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_job_changes();
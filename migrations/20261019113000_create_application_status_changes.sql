@@ -0,0 +1,17 @@
+-- Add migration script here
+ALTER TABLE applications DROP CONSTRAINT applications_status_check;
+ALTER TABLE applications ADD CONSTRAINT applications_status_check
+    CHECK (status IN ('pending', 'reviewing', 'interviewing', 'offered', 'accepted', 'rejected'));
+
+CREATE TABLE application_status_changes (
+    id SERIAL PRIMARY KEY,
+    application_id INTEGER NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    -- NULL when the change was made automatically, e.g. by a knock-out rule.
+    changed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX application_status_changes_application_id_idx ON application_status_changes (application_id, created_at);
@@ -0,0 +1,56 @@
+-- Add migration script here
+CREATE TABLE pipeline_stages (
+    id SERIAL PRIMARY KEY,
+    -- NULL for the default pipeline, used by companies without one of their own.
+    company_id INTEGER REFERENCES companies(id) ON DELETE CASCADE,
+    key TEXT NOT NULL,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    terminal BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX pipeline_stages_company_key_idx ON pipeline_stages (COALESCE(company_id, 0), key);
+CREATE UNIQUE INDEX pipeline_stages_company_position_idx ON pipeline_stages (COALESCE(company_id, 0), position);
+
+INSERT INTO pipeline_stages (company_id, key, name, position, terminal) VALUES
+    (NULL, 'applied', 'Applied', 0, FALSE),
+    (NULL, 'screening', 'Screening', 1, FALSE),
+    (NULL, 'phone_screen', 'Phone screen', 2, FALSE),
+    (NULL, 'interview', 'Interview', 3, FALSE),
+    (NULL, 'offer', 'Offer', 4, FALSE),
+    (NULL, 'hired', 'Hired', 5, TRUE),
+    (NULL, 'rejected', 'Rejected', 6, TRUE),
+    (NULL, 'withdrawn', 'Withdrawn', 7, TRUE);
+
+-- Statuses are now stage keys from the company's pipeline.
+ALTER TABLE applications DROP CONSTRAINT applications_status_check;
+ALTER TABLE applications ALTER COLUMN status SET DEFAULT 'applied';
+
+UPDATE applications
+SET status = CASE status
+    WHEN 'pending' THEN 'applied'
+    WHEN 'reviewing' THEN 'screening'
+    WHEN 'interviewing' THEN 'interview'
+    WHEN 'offered' THEN 'offer'
+    WHEN 'accepted' THEN 'hired'
+    ELSE status
+END;
+
+UPDATE application_status_changes
+SET from_status = CASE from_status
+        WHEN 'pending' THEN 'applied'
+        WHEN 'reviewing' THEN 'screening'
+        WHEN 'interviewing' THEN 'interview'
+        WHEN 'offered' THEN 'offer'
+        WHEN 'accepted' THEN 'hired'
+        ELSE from_status
+    END,
+    to_status = CASE to_status
+        WHEN 'pending' THEN 'applied'
+        WHEN 'reviewing' THEN 'screening'
+        WHEN 'interviewing' THEN 'interview'
+        WHEN 'offered' THEN 'offer'
+        WHEN 'accepted' THEN 'hired'
+        ELSE to_status
+    END;
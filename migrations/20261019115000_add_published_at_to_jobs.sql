@@ -0,0 +1,11 @@
+-- Add migration script here
+-- When the posting first became visible: open and approved. Saved-search
+-- alerts key on this rather than created_at, so postings that sat in the
+-- moderation queue are still alerted once approved. Stored in UTC.
+ALTER TABLE jobs ADD COLUMN published_at TIMESTAMP;
+
+UPDATE jobs
+SET published_at = created_at
+WHERE moderation_status = 'approved' AND status <> 'draft';
+
+CREATE INDEX jobs_published_at_idx ON jobs (published_at);
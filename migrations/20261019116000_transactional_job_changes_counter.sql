@@ -0,0 +1,20 @@
+-- Add migration script here
+-- A sequence bumps as soon as the statement runs and never rolls back, so a
+-- reader could see the new value while the change itself was uncommitted.
+-- A one-row table is updated inside the writer's transaction instead, and
+-- readers only ever see committed generations.
+CREATE TABLE job_changes (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    generation BIGINT NOT NULL
+);
+
+INSERT INTO job_changes (generation) SELECT last_value FROM job_changes_seq;
+
+CREATE OR REPLACE FUNCTION bump_job_changes() RETURNS trigger AS $$
+BEGIN
+    UPDATE job_changes SET generation = generation + 1;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP SEQUENCE job_changes_seq;
@@ -0,0 +1,7 @@
+-- Add migration script here
+-- Extend links stop working after a while, even if never used. Stored in UTC.
+ALTER TABLE jobs ADD COLUMN extend_token_expires_at TIMESTAMP;
+
+UPDATE jobs
+SET extend_token_expires_at = (now() AT TIME ZONE 'UTC') + INTERVAL '14 days'
+WHERE extend_token IS NOT NULL;
@@ -0,0 +1,8 @@
+-- Add migration script here
+-- Soft-deleted accounts no longer hold on to their email and username, so
+-- the same person can sign up again during the retention window.
+ALTER TABLE users DROP CONSTRAINT users_email_key;
+ALTER TABLE users DROP CONSTRAINT users_username_key;
+
+CREATE UNIQUE INDEX users_email_key ON users (email) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX users_username_key ON users (username) WHERE deleted_at IS NULL;
@@ -0,0 +1,7 @@
+-- Add migration script here
+-- Jobs belong to their company; purging the recruiter who posted one must not
+-- take the posting and its applications with it.
+ALTER TABLE jobs
+    ALTER COLUMN employer_id DROP NOT NULL,
+    DROP CONSTRAINT jobs_employer_id_fkey,
+    ADD CONSTRAINT jobs_employer_id_fkey FOREIGN KEY (employer_id) REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,4 @@
+-- Add migration script here
+-- last_run_at is compared against UTC publish times and the scheduler's UTC
+-- clock, so new searches must start from UTC now, not session-local time.
+ALTER TABLE saved_searches ALTER COLUMN last_run_at SET DEFAULT (now() AT TIME ZONE 'UTC');
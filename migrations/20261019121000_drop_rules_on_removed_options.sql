@@ -0,0 +1,8 @@
+-- Add migration script here
+-- Rules left pointing at an option their question no longer offers would
+-- reject every applicant.
+DELETE FROM knockout_rules r
+USING job_questions q
+WHERE q.id = r.question_id
+  AND q.kind = 'multiple_choice'
+  AND r.value <> ALL(q.options);
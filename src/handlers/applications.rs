@@ -1,16 +1,28 @@
+use crate::handlers::job_questions::{AnswerRequest, validate_answers};
+use crate::handlers::knockout_rules::apply_knockout_rules;
 use crate::models::application::Application;
-use crate::utils::auth::get_user_from_token;
+use crate::models::application_status_change::ApplicationStatusChange;
+use crate::models::job::Job;
+use crate::models::job_question::{ApplicationAnswer, JobQuestion};
+use crate::models::notification::Notification;
+use crate::models::pipeline_stage::{PipelineStage, can_transition};
+use crate::utils::auth::{can_edit_job, can_view_application, get_user_from_token};
+use crate::utils::etag::{etag, if_match_version, precondition_failed};
+use crate::utils::pagination::{Page, Pagination};
 use actix_web::{
-    HttpResponse,
-    web::{Data, Json, Path},
+    HttpRequest, HttpResponse,
+    http::header::ETAG,
+    web::{Data, Json, Path, Query},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 #[derive(Deserialize)]
 pub struct CreateApplicationRequest {
     pub job_id: i32,
     pub message: String,
+    #[serde(default)]
+    pub answers: Vec<AnswerRequest>,
 }
 
 #[derive(Deserialize)]
@@ -18,6 +30,32 @@ pub struct UpdateApplicationRequest {
     pub message: String,
 }
 
+const MAX_REASON_LENGTH: usize = 2000;
+
+#[derive(Deserialize)]
+pub struct UpdateApplicationStatusRequest {
+    pub status: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ApplicationFilter {
+    /// A stage key from the job's pipeline.
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PatchApplicationRequest {
+    pub message: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ApplicationDetail {
+    #[serde(flatten)]
+    pub application: Application,
+    pub answers: Vec<ApplicationAnswer>,
+}
+
 pub async fn create_application(
     pool: Data<PgPool>,
     form: Json<CreateApplicationRequest>,
@@ -28,38 +66,237 @@ pub async fn create_application(
         Err(response) => return response,
     };
 
-    match Application::create(&pool, &user.id, &form.job_id, &form.message).await {
-        Ok(application) => HttpResponse::Ok().json(application),
-        Err(_) => HttpResponse::InternalServerError().json("Failed to create application"),
+    let job = match Job::find_by_id(&pool, &form.job_id).await {
+        Ok(job) if job.moderation_status != "approved" => {
+            return HttpResponse::NotFound().json("Job not found");
+        }
+        Ok(job) if job.status == "open" => job,
+        Ok(_) => {
+            return HttpResponse::BadRequest().json("This job is no longer accepting applications");
+        }
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    let questions = match JobQuestion::find_by_job_id(&pool, &form.job_id).await {
+        Ok(questions) => questions,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to create application"),
+    };
+    let answers = match validate_answers(&questions, &form.answers) {
+        Ok(answers) => answers,
+        Err(response) => return response,
+    };
+
+    let stages = match PipelineStage::find_for_company(&pool, &job.company_id).await {
+        Ok(stages) => stages,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to create application"),
+    };
+    let Some(first_stage) = stages.first() else {
+        return HttpResponse::InternalServerError().json("Failed to create application");
+    };
+
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to create application"),
+    };
+    let application = match Application::create_in(
+        &mut tx,
+        &user.id,
+        &form.job_id,
+        &form.message,
+        &answers,
+        &first_stage.key,
+    )
+    .await
+    {
+        Ok(application) => application,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to create application"),
+    };
+    // Evaluated before committing, so no application is ever left unevaluated.
+    let application =
+        match apply_knockout_rules(&pool, &mut tx, application, &questions, &answers).await {
+            Ok(application) => application,
+            Err(_) => {
+                return HttpResponse::InternalServerError().json("Failed to evaluate application");
+            }
+        };
+    if tx.commit().await.is_err() {
+        return HttpResponse::InternalServerError().json("Failed to create application");
     }
+
+    HttpResponse::Ok().json(ApplicationDetail {
+        application,
+        answers,
+    })
 }
 
-pub async fn get_application_by_id(pool: Data<PgPool>, application_id: Path<i32>) -> HttpResponse {
-    match Application::find_by_id(&pool, &application_id).await {
-        Ok(application) => HttpResponse::Ok().json(application),
-        Err(_) => HttpResponse::NotFound().json("Application not found"),
+pub async fn get_application_by_id(
+    pool: Data<PgPool>,
+    application_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let application = match Application::find_by_id(&pool, &application_id).await {
+        Ok(application) => application,
+        Err(_) => return HttpResponse::NotFound().json("Application not found"),
+    };
+
+    if !can_view_application(&pool, &user, &application).await {
+        return HttpResponse::Forbidden()
+            .json("You do not have permission to view this application");
+    }
+
+    match ApplicationAnswer::find_by_application_id(&pool, &application.id).await {
+        Ok(answers) => HttpResponse::Ok()
+            .insert_header((ETAG, etag(application.version)))
+            .json(ApplicationDetail {
+                application,
+                answers,
+            }),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get application"),
     }
 }
 
-pub async fn get_applications(pool: Data<PgPool>) -> HttpResponse {
-    match Application::find_all(&pool).await {
+/// Every application for admins; for anyone else their own applications and
+/// those to jobs of the companies they belong to.
+pub async fn get_applications(pool: Data<PgPool>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let applications = if user.role == "admin" {
+        Application::find_all(&pool).await
+    } else {
+        Application::find_visible_to(&pool, &user.id).await
+    };
+    match applications {
         Ok(applications) => HttpResponse::Ok().json(applications),
         Err(_) => HttpResponse::InternalServerError().json("Failed to get applications"),
     }
 }
 
+/// The job's applications, for the hiring company's members and admins,
+/// optionally only those at one pipeline stage.
+pub async fn get_job_applications(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    filter: Query<ApplicationFilter>,
+    pagination: Query<Pagination>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if user.role != "admin" && !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden()
+            .json("You do not have permission to view this job's applications");
+    }
+
+    let status = filter.status.as_deref().map(str::trim);
+    if let Some(status) = status {
+        let stages = match PipelineStage::find_for_company(&pool, &job.company_id).await {
+            Ok(stages) => stages,
+            Err(_) => {
+                return HttpResponse::InternalServerError().json("Failed to get applications");
+            }
+        };
+        if !stages.iter().any(|stage| stage.key == status) {
+            let keys: Vec<&str> = stages.iter().map(|stage| stage.key.as_str()).collect();
+            return HttpResponse::BadRequest()
+                .json(format!("status must be one of: {}", keys.join(", ")));
+        }
+    }
+
+    let applications = match Application::find_by_job_id(
+        &pool,
+        &job.id,
+        status,
+        pagination.per_page(),
+        pagination.offset(),
+    )
+    .await
+    {
+        Ok(applications) => applications,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to get applications"),
+    };
+    let total = match Application::count_by_job_id(&pool, &job.id, status).await {
+        Ok(total) => total,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to get applications"),
+    };
+
+    HttpResponse::Ok().json(Page::new(applications, &pagination, total))
+}
+
 pub async fn update_application(
     pool: Data<PgPool>,
     application_id: Path<i32>,
     form: Json<UpdateApplicationRequest>,
     token: String,
+    req: HttpRequest,
 ) -> HttpResponse {
-    let user = match get_user_from_token(&pool, &token).await {
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+
+    save_application_message(
+        &pool,
+        &application_id,
+        Some(&form.message),
+        expected_version,
+        &token,
+    )
+    .await
+}
+
+pub async fn patch_application(
+    pool: Data<PgPool>,
+    application_id: Path<i32>,
+    form: Json<PatchApplicationRequest>,
+    token: String,
+    req: HttpRequest,
+) -> HttpResponse {
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+
+    save_application_message(
+        &pool,
+        &application_id,
+        form.message.as_deref(),
+        expected_version,
+        &token,
+    )
+    .await
+}
+
+/// Shared by PUT and PATCH. A patch without a message just returns the
+/// application as it stands.
+async fn save_application_message(
+    pool: &PgPool,
+    application_id: &i32,
+    message: Option<&str>,
+    expected_version: Option<i32>,
+    token: &str,
+) -> HttpResponse {
+    let user = match get_user_from_token(pool, token).await {
         Ok(user) => user,
         Err(response) => return response,
     };
 
-    let application = match Application::find_by_id(&pool, &application_id).await {
+    let application = match Application::find_by_id(pool, application_id).await {
         Ok(application) => application,
         Err(_) => return HttpResponse::NotFound().json("Application not found"),
     };
@@ -69,12 +306,164 @@ pub async fn update_application(
             .json("You do not have permission to update this application");
     }
 
-    match Application::update(&pool, &application_id, &form.message).await {
-        Ok(application) => HttpResponse::Ok().json(application),
+    if expected_version.is_some_and(|version| version != application.version) {
+        return precondition_failed();
+    }
+
+    let Some(message) = message else {
+        return HttpResponse::Ok()
+            .insert_header((ETAG, etag(application.version)))
+            .json(application);
+    };
+
+    match Application::update(pool, application_id, message, expected_version).await {
+        Ok(Some(application)) => HttpResponse::Ok()
+            .insert_header((ETAG, etag(application.version)))
+            .json(application),
+        Ok(None) => precondition_failed(),
         Err(_) => HttpResponse::InternalServerError().json("Failed to update application"),
     }
 }
 
+/// Moves the application to another stage of the company's pipeline. Only
+/// members of the hiring company may do this, and only along the moves
+/// allowed by `can_transition`.
+pub async fn update_application_status(
+    pool: Data<PgPool>,
+    application_id: Path<i32>,
+    form: Json<UpdateApplicationStatusRequest>,
+    token: String,
+    req: HttpRequest,
+) -> HttpResponse {
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let application = match Application::find_by_id(&pool, &application_id).await {
+        Ok(application) => application,
+        Err(_) => return HttpResponse::NotFound().json("Application not found"),
+    };
+    let job = match Job::find_by_id(&pool, &application.job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Application not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden()
+            .json("You do not have permission to change this application's status");
+    }
+
+    let stages = match PipelineStage::find_for_company(&pool, &job.company_id).await {
+        Ok(stages) => stages,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json("Failed to update application status");
+        }
+    };
+    let status = form.status.trim();
+    let Some(stage) = stages.iter().find(|stage| stage.key == status) else {
+        let keys: Vec<&str> = stages.iter().map(|stage| stage.key.as_str()).collect();
+        return HttpResponse::BadRequest()
+            .json(format!("status must be one of: {}", keys.join(", ")));
+    };
+    let reason = form
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    if reason.is_some_and(|reason| reason.len() > MAX_REASON_LENGTH) {
+        return HttpResponse::BadRequest().json(format!(
+            "reason must be at most {MAX_REASON_LENGTH} characters"
+        ));
+    }
+
+    if expected_version.is_some_and(|version| version != application.version) {
+        return precondition_failed();
+    }
+
+    if !can_transition(&stages, &application.status, status) {
+        return HttpResponse::Conflict().json(format!(
+            "Cannot move an application from {} to {status}",
+            application.status
+        ));
+    }
+
+    let updated = match Application::update_status(
+        &pool,
+        &application.id,
+        &application.status,
+        status,
+        Some(user.id),
+        reason,
+        expected_version,
+    )
+    .await
+    {
+        Ok(Some(application)) => application,
+        Ok(None) if expected_version.is_some() => return precondition_failed(),
+        Ok(None) => {
+            return HttpResponse::Conflict()
+                .json("The application's status changed in the meantime");
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json("Failed to update application status");
+        }
+    };
+
+    let message = format!(
+        "Your application for \"{}\" is now at the {} stage",
+        job.title, stage.name
+    );
+    if Notification::create(
+        &pool,
+        &updated.user_id,
+        "application_status",
+        Some(job.id),
+        &message,
+    )
+    .await
+    .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to notify the applicant");
+    }
+
+    HttpResponse::Ok()
+        .insert_header((ETAG, etag(updated.version)))
+        .json(updated)
+}
+
+/// Status history for an application, visible to whoever can see it.
+pub async fn get_application_status_history(
+    pool: Data<PgPool>,
+    application_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let application = match Application::find_by_id(&pool, &application_id).await {
+        Ok(application) => application,
+        Err(_) => return HttpResponse::NotFound().json("Application not found"),
+    };
+
+    if !can_view_application(&pool, &user, &application).await {
+        return HttpResponse::Forbidden()
+            .json("You do not have permission to view this application");
+    }
+
+    match ApplicationStatusChange::find_by_application_id(&pool, &application.id).await {
+        Ok(changes) => HttpResponse::Ok().json(changes),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get status history"),
+    }
+}
+
 pub async fn delete_application(
     pool: Data<PgPool>,
     application_id: Path<i32>,
@@ -28,6 +28,10 @@ pub struct AuthResponse {
 }
 
 pub async fn register(pool: Data<PgPool>, form: Json<RegisterRequest>) -> HttpResponse {
+    if form.role != "job_seeker" && form.role != "employer" {
+        return HttpResponse::BadRequest().json("Role must be job_seeker or employer");
+    }
+
     let password_hash = hash(&form.password, DEFAULT_COST).unwrap();
     match User::create(
         &pool,
@@ -0,0 +1,139 @@
+use crate::models::category::Category;
+use crate::utils::auth::get_admin_from_token;
+use crate::utils::slug::slugify;
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct CreateCategoryRequest {
+    pub name: String,
+    pub slug: Option<String>,
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateCategoryRequest {
+    pub name: String,
+    pub slug: Option<String>,
+    pub parent_id: Option<i32>,
+}
+
+async fn category_slug(
+    pool: &PgPool,
+    name: &str,
+    slug: &Option<String>,
+) -> Result<String, HttpResponse> {
+    let slug = match slugify(pool, slug.as_deref().unwrap_or(name)).await {
+        Ok(slug) => slug,
+        Err(_) => return Err(HttpResponse::InternalServerError().json("Failed to derive slug")),
+    };
+    if slug.is_empty() {
+        return Err(HttpResponse::BadRequest().json("Category name must contain letters or digits"));
+    }
+    Ok(slug)
+}
+
+pub async fn get_categories(pool: Data<PgPool>) -> HttpResponse {
+    match Category::find_tree(&pool).await {
+        Ok(categories) => HttpResponse::Ok().json(categories),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get categories"),
+    }
+}
+
+pub async fn get_category_by_id(pool: Data<PgPool>, category_id: Path<i32>) -> HttpResponse {
+    match Category::find_by_id(&pool, &category_id).await {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(_) => HttpResponse::NotFound().json("Category not found"),
+    }
+}
+
+pub async fn create_category(
+    pool: Data<PgPool>,
+    form: Json<CreateCategoryRequest>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    let slug = match category_slug(&pool, &form.name, &form.slug).await {
+        Ok(slug) => slug,
+        Err(response) => return response,
+    };
+
+    if let Some(parent_id) = form.parent_id
+        && Category::find_by_id(&pool, &parent_id).await.is_err()
+    {
+        return HttpResponse::BadRequest().json("Parent category not found");
+    }
+
+    match Category::create(&pool, form.parent_id, form.name.trim(), &slug).await {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(_) => HttpResponse::Conflict().json("A category with this slug already exists"),
+    }
+}
+
+pub async fn update_category(
+    pool: Data<PgPool>,
+    category_id: Path<i32>,
+    form: Json<UpdateCategoryRequest>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    if Category::find_by_id(&pool, &category_id).await.is_err() {
+        return HttpResponse::NotFound().json("Category not found");
+    }
+
+    let slug = match category_slug(&pool, &form.name, &form.slug).await {
+        Ok(slug) => slug,
+        Err(response) => return response,
+    };
+
+    if let Some(parent_id) = form.parent_id {
+        match Category::is_descendant_of(&pool, &parent_id, &category_id).await {
+            Ok(false) => {}
+            Ok(true) => {
+                return HttpResponse::BadRequest()
+                    .json("A category cannot be moved under itself or its descendants");
+            }
+            Err(_) => {
+                return HttpResponse::InternalServerError().json("Failed to update category");
+            }
+        }
+        if Category::find_by_id(&pool, &parent_id).await.is_err() {
+            return HttpResponse::BadRequest().json("Parent category not found");
+        }
+    }
+
+    match Category::update(&pool, &category_id, form.parent_id, form.name.trim(), &slug).await {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(_) => HttpResponse::Conflict().json("A category with this slug already exists"),
+    }
+}
+
+pub async fn delete_category(
+    pool: Data<PgPool>,
+    category_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    if Category::find_by_id(&pool, &category_id).await.is_err() {
+        return HttpResponse::NotFound().json("Category not found");
+    }
+
+    match Category::delete(&pool, &category_id).await {
+        Ok(_) => HttpResponse::Ok().json("Category deleted"),
+        Err(_) => HttpResponse::Conflict()
+            .json("Category still has jobs or subcategories and cannot be deleted"),
+    }
+}
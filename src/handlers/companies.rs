@@ -0,0 +1,378 @@
+use crate::models::company::{Company, CompanyInput, CompanyMember};
+use crate::models::job::Job;
+use crate::models::user::User;
+use crate::services::translations::localize;
+use crate::utils::auth::get_user_from_token;
+use crate::utils::locale::requested_locales;
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, Path},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+const COMPANY_SIZES: [&str; 6] = ["1-10", "11-50", "51-200", "201-500", "501-1000", "1000+"];
+pub const MEMBER_ROLES: [&str; 3] = ["owner", "admin", "recruiter"];
+
+#[derive(Deserialize)]
+pub struct CompanyRequest {
+    pub name: String,
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    pub size: Option<String>,
+    pub industry: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AddMemberRequest {
+    pub email: String,
+    pub role: String,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMemberRequest {
+    pub role: String,
+}
+
+fn company_input(form: &CompanyRequest) -> Result<CompanyInput, HttpResponse> {
+    if form.name.trim().is_empty() {
+        return Err(HttpResponse::BadRequest().json("Company name must not be empty"));
+    }
+    if let Some(size) = &form.size
+        && !COMPANY_SIZES.contains(&size.as_str())
+    {
+        return Err(HttpResponse::BadRequest().json(format!(
+            "Company size must be one of: {}",
+            COMPANY_SIZES.join(", ")
+        )));
+    }
+
+    Ok(CompanyInput {
+        name: form.name.trim().to_string(),
+        logo_url: form.logo_url.clone(),
+        website: form.website.clone(),
+        description: form.description.clone(),
+        size: form.size.clone(),
+        industry: form.industry.clone(),
+    })
+}
+
+/// Returns the caller's role in the company, failing unless it is one of `allowed`.
+pub async fn require_member_role(
+    pool: &PgPool,
+    company_id: &i32,
+    user_id: &i32,
+    allowed: &[&str],
+) -> Result<String, HttpResponse> {
+    match CompanyMember::find_role(pool, company_id, user_id).await {
+        Ok(Some(role)) if allowed.contains(&role.as_str()) => Ok(role),
+        Ok(_) => {
+            Err(HttpResponse::Forbidden().json("You do not have permission to manage this company"))
+        }
+        Err(_) => {
+            Err(HttpResponse::InternalServerError().json("Failed to check company membership"))
+        }
+    }
+}
+
+pub async fn create_company(
+    pool: Data<PgPool>,
+    form: Json<CompanyRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if user.role != "employer" {
+        return HttpResponse::Forbidden().json("Only employers can create companies");
+    }
+
+    let input = match company_input(&form) {
+        Ok(input) => input,
+        Err(response) => return response,
+    };
+
+    match Company::create(&pool, &user.id, &input).await {
+        Ok(company) => HttpResponse::Ok().json(company),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to create company"),
+    }
+}
+
+pub async fn get_company_by_id(pool: Data<PgPool>, company_id: Path<i32>) -> HttpResponse {
+    match Company::find_by_id(&pool, &company_id).await {
+        Ok(company) => HttpResponse::Ok().json(company),
+        Err(_) => HttpResponse::NotFound().json("Company not found"),
+    }
+}
+
+pub async fn get_jobs_of_company(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    req: HttpRequest,
+) -> HttpResponse {
+    match Job::find_by_company_id(&pool, &company_id).await {
+        Ok(jobs) => {
+            let mut jobs: Vec<Job> = jobs
+                .into_iter()
+                .filter(|job| job.moderation_status == "approved")
+                .collect();
+            if localize(&pool, jobs.iter_mut().collect(), &requested_locales(&req))
+                .await
+                .is_err()
+            {
+                return HttpResponse::InternalServerError().json("Failed to get jobs");
+            }
+            HttpResponse::Ok().json(jobs)
+        }
+        Err(_) => HttpResponse::NotFound().json("Jobs not found"),
+    }
+}
+
+/// The company's unpublished postings, visible to its members only.
+pub async fn get_company_drafts(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = require_member_role(&pool, &company_id, &user.id, &MEMBER_ROLES).await {
+        return response;
+    }
+
+    match Job::find_by_company_id(&pool, &company_id).await {
+        Ok(jobs) => {
+            let drafts: Vec<Job> = jobs
+                .into_iter()
+                .filter(|job| job.status == "draft")
+                .collect();
+            HttpResponse::Ok().json(drafts)
+        }
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get drafts"),
+    }
+}
+
+pub async fn get_my_companies(pool: Data<PgPool>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match Company::find_by_user_id(&pool, &user.id).await {
+        Ok(companies) => HttpResponse::Ok().json(companies),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get companies"),
+    }
+}
+
+pub async fn update_company(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    form: Json<CompanyRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if Company::find_by_id(&pool, &company_id).await.is_err() {
+        return HttpResponse::NotFound().json("Company not found");
+    }
+
+    if let Err(response) =
+        require_member_role(&pool, &company_id, &user.id, &["owner", "admin"]).await
+    {
+        return response;
+    }
+
+    let input = match company_input(&form) {
+        Ok(input) => input,
+        Err(response) => return response,
+    };
+
+    match Company::update(&pool, &company_id, &input).await {
+        Ok(company) => HttpResponse::Ok().json(company),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to update company"),
+    }
+}
+
+pub async fn get_company_members(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = require_member_role(&pool, &company_id, &user.id, &MEMBER_ROLES).await {
+        return response;
+    }
+
+    match CompanyMember::find_by_company_id(&pool, &company_id).await {
+        Ok(members) => HttpResponse::Ok().json(members),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get company members"),
+    }
+}
+
+pub async fn add_company_member(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    form: Json<AddMemberRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let caller_role =
+        match require_member_role(&pool, &company_id, &user.id, &["owner", "admin"]).await {
+            Ok(role) => role,
+            Err(response) => return response,
+        };
+
+    if !MEMBER_ROLES.contains(&form.role.as_str()) {
+        return HttpResponse::BadRequest().json("Role must be owner, admin or recruiter");
+    }
+    if form.role != "recruiter" && caller_role != "owner" {
+        return HttpResponse::Forbidden().json("Only owners can grant owner or admin roles");
+    }
+
+    let member = match User::find_by_email(&pool, &form.email).await {
+        Ok(member) => member,
+        Err(_) => return HttpResponse::NotFound().json("User not found"),
+    };
+
+    if member.role != "employer" {
+        return HttpResponse::BadRequest().json("Only employer accounts can join a company");
+    }
+
+    match CompanyMember::find_role(&pool, &company_id, &member.id).await {
+        Ok(None) => {}
+        Ok(Some(_)) => return HttpResponse::Conflict().json("User is already a member"),
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to add member"),
+    }
+
+    if CompanyMember::upsert(&pool, &company_id, &member.id, &form.role)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to add member");
+    }
+
+    match CompanyMember::find_by_company_id(&pool, &company_id).await {
+        Ok(members) => HttpResponse::Ok().json(members),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get company members"),
+    }
+}
+
+pub async fn update_company_member(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    form: Json<UpdateMemberRequest>,
+    token: String,
+) -> HttpResponse {
+    let (company_id, member_id) = path.into_inner();
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = require_member_role(&pool, &company_id, &user.id, &["owner"]).await {
+        return response;
+    }
+
+    if !MEMBER_ROLES.contains(&form.role.as_str()) {
+        return HttpResponse::BadRequest().json("Role must be owner, admin or recruiter");
+    }
+
+    match CompanyMember::find_role(&pool, &company_id, &member_id).await {
+        Ok(Some(role)) => {
+            if role == "owner" && form.role != "owner" {
+                match CompanyMember::count_owners(&pool, &company_id).await {
+                    Ok(owners) if owners > 1 => {}
+                    Ok(_) => {
+                        return HttpResponse::BadRequest()
+                            .json("A company must keep at least one owner");
+                    }
+                    Err(_) => {
+                        return HttpResponse::InternalServerError().json("Failed to update member");
+                    }
+                }
+            }
+        }
+        Ok(None) => return HttpResponse::NotFound().json("Member not found"),
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to update member"),
+    }
+
+    if CompanyMember::upsert(&pool, &company_id, &member_id, &form.role)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to update member");
+    }
+
+    match CompanyMember::find_by_company_id(&pool, &company_id).await {
+        Ok(members) => HttpResponse::Ok().json(members),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get company members"),
+    }
+}
+
+pub async fn remove_company_member(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    token: String,
+) -> HttpResponse {
+    let (company_id, member_id) = path.into_inner();
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    // Members may always leave; removing someone else takes an owner or admin.
+    if member_id != user.id
+        && let Err(response) =
+            require_member_role(&pool, &company_id, &user.id, &["owner", "admin"]).await
+    {
+        return response;
+    }
+
+    match CompanyMember::find_role(&pool, &company_id, &member_id).await {
+        Ok(Some(role)) if role == "owner" => {
+            match CompanyMember::count_owners(&pool, &company_id).await {
+                Ok(owners) if owners > 1 => {}
+                Ok(_) => {
+                    return HttpResponse::BadRequest()
+                        .json("A company must keep at least one owner");
+                }
+                Err(_) => {
+                    return HttpResponse::InternalServerError().json("Failed to remove member");
+                }
+            }
+            if member_id != user.id
+                && require_member_role(&pool, &company_id, &user.id, &["owner"])
+                    .await
+                    .is_err()
+            {
+                return HttpResponse::Forbidden().json("Only owners can remove an owner");
+            }
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json("Member not found"),
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to remove member"),
+    }
+
+    match CompanyMember::delete(&pool, &company_id, &member_id).await {
+        Ok(_) => HttpResponse::Ok().json("Member removed"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to remove member"),
+    }
+}
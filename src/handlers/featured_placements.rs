@@ -0,0 +1,137 @@
+use crate::models::featured_placement::FeaturedPlacement;
+use crate::models::job::Job;
+use crate::services::analytics::AnalyticsRecorder;
+use crate::utils::auth::{can_edit_job, get_admin_from_token, get_user_from_token};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, Path},
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+const MAX_WEIGHT: i32 = 100;
+
+#[derive(Deserialize)]
+pub struct FeaturedPlacementRequest {
+    /// Defaults to now.
+    pub starts_at: Option<NaiveDateTime>,
+    pub ends_at: NaiveDateTime,
+    #[serde(default = "default_weight")]
+    pub weight: i32,
+}
+
+fn default_weight() -> i32 {
+    1
+}
+
+/// Books a featured window for the job. Placements are paid for outside the
+/// platform, so only admins create them.
+pub async fn create_featured_placement(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    form: Json<FeaturedPlacementRequest>,
+    token: String,
+) -> HttpResponse {
+    let admin = match get_admin_from_token(&pool, &token).await {
+        Ok(admin) => admin,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if job.moderation_status != "approved" {
+        return HttpResponse::Conflict().json("Only approved jobs can be featured");
+    }
+
+    let now = Utc::now().naive_utc();
+    let starts_at = form.starts_at.unwrap_or(now);
+    if form.ends_at <= starts_at {
+        return HttpResponse::BadRequest().json("ends_at must be after starts_at");
+    }
+    if form.ends_at <= now {
+        return HttpResponse::BadRequest().json("ends_at must be in the future");
+    }
+    if !(1..=MAX_WEIGHT).contains(&form.weight) {
+        return HttpResponse::BadRequest()
+            .json(format!("weight must be between 1 and {MAX_WEIGHT}"));
+    }
+
+    match FeaturedPlacement::create(
+        &pool,
+        &job.id,
+        starts_at,
+        form.ends_at,
+        form.weight,
+        &admin.id,
+    )
+    .await
+    {
+        Ok(placement) => HttpResponse::Ok().json(placement),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to create featured placement"),
+    }
+}
+
+pub async fn get_featured_placements(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if user.role != "admin" && !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden()
+            .json("You do not have permission to view this job's placements");
+    }
+
+    match FeaturedPlacement::find_by_job_id(&pool, &job.id).await {
+        Ok(placements) => HttpResponse::Ok().json(placements),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get featured placements"),
+    }
+}
+
+pub async fn delete_featured_placement(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    let (job_id, placement_id) = path.into_inner();
+    match FeaturedPlacement::delete(&pool, &job_id, &placement_id).await {
+        Ok(true) => HttpResponse::Ok().json("Featured placement deleted"),
+        Ok(false) => HttpResponse::NotFound().json("Featured placement not found"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to delete featured placement"),
+    }
+}
+
+/// Counts a click on the job from a featured slot. Clicks while the job has
+/// no running placement are ignored.
+pub async fn record_featured_click(
+    pool: Data<PgPool>,
+    analytics: Data<AnalyticsRecorder>,
+    job_id: Path<i32>,
+    req: HttpRequest,
+) -> HttpResponse {
+    match FeaturedPlacement::find_active(&pool, &[*job_id]).await {
+        Ok(placements) if !placements.is_empty() => {
+            analytics.record_featured_click(*job_id, &req);
+            HttpResponse::Accepted().finish()
+        }
+        Ok(_) => HttpResponse::NotFound().json("Job is not featured"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to record click"),
+    }
+}
@@ -0,0 +1,132 @@
+use crate::models::category::Category;
+use crate::models::job_feed::FeedJob;
+use crate::services::feeds::{
+    app_url, job_posting_jsonld, render_aggregator, render_atom, render_jsonld, render_rss,
+};
+use crate::utils::etag::{content_etag, if_none_match};
+use crate::utils::pagination::Pagination;
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::header::{CACHE_CONTROL, ETAG},
+    web::{Data, Path, Query},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+const FEED_SIZE: i64 = 50;
+const FEED_MAX_AGE_SECONDS: u32 = 300;
+
+#[derive(Deserialize)]
+pub struct FeedQuery {
+    pub category_id: Option<i32>,
+}
+
+/// Sends `body` with caching headers, or an empty 304 when the client's
+/// `If-None-Match` already names the same content.
+fn cached_response(req: &HttpRequest, content_type: &str, body: String) -> HttpResponse {
+    let tag = content_etag(&body);
+    let cache_control = format!("public, max-age={FEED_MAX_AGE_SECONDS}");
+    if if_none_match(req, &tag) {
+        return HttpResponse::NotModified()
+            .insert_header((ETAG, tag))
+            .insert_header((CACHE_CONTROL, cache_control))
+            .finish();
+    }
+
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header((ETAG, tag))
+        .insert_header((CACHE_CONTROL, cache_control))
+        .body(body)
+}
+
+fn feed_url(req: &HttpRequest) -> String {
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or(req.path());
+    format!("{}{}", app_url(), path)
+}
+
+async fn find_feed_jobs(pool: &PgPool, query: &FeedQuery) -> Result<Vec<FeedJob>, HttpResponse> {
+    if let Some(category_id) = query.category_id
+        && Category::find_by_id(pool, &category_id).await.is_err()
+    {
+        return Err(HttpResponse::NotFound().json("Category not found"));
+    }
+
+    FeedJob::find_recent(pool, query.category_id, FEED_SIZE, 0)
+        .await
+        .map_err(|_| HttpResponse::InternalServerError().json("Failed to build feed"))
+}
+
+pub async fn get_job_jsonld(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    req: HttpRequest,
+) -> HttpResponse {
+    match FeedJob::find_by_id(&pool, &job_id).await {
+        Ok(job) => cached_response(
+            &req,
+            "application/ld+json",
+            render_jsonld(&job_posting_jsonld(&job)),
+        ),
+        Err(_) => HttpResponse::NotFound().json("Job not found"),
+    }
+}
+
+pub async fn get_rss_feed(
+    pool: Data<PgPool>,
+    query: Query<FeedQuery>,
+    req: HttpRequest,
+) -> HttpResponse {
+    match find_feed_jobs(&pool, &query).await {
+        Ok(jobs) => cached_response(
+            &req,
+            "application/rss+xml; charset=utf-8",
+            render_rss(&jobs, &feed_url(&req)),
+        ),
+        Err(response) => response,
+    }
+}
+
+pub async fn get_atom_feed(
+    pool: Data<PgPool>,
+    query: Query<FeedQuery>,
+    req: HttpRequest,
+) -> HttpResponse {
+    match find_feed_jobs(&pool, &query).await {
+        Ok(jobs) => cached_response(
+            &req,
+            "application/atom+xml; charset=utf-8",
+            render_atom(&jobs, &feed_url(&req)),
+        ),
+        Err(response) => response,
+    }
+}
+
+/// Every live posting, a page at a time, for aggregators that crawl the
+/// whole board.
+pub async fn get_aggregator_feed(
+    pool: Data<PgPool>,
+    pagination: Query<Pagination>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let total = match FeedJob::count(&pool, None).await {
+        Ok(total) => total,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to build feed"),
+    };
+    let jobs =
+        match FeedJob::find_recent(&pool, None, pagination.per_page(), pagination.offset()).await {
+            Ok(jobs) => jobs,
+            Err(_) => return HttpResponse::InternalServerError().json("Failed to build feed"),
+        };
+
+    let total_pages = (total + pagination.per_page() - 1) / pagination.per_page();
+    cached_response(
+        &req,
+        "application/xml; charset=utf-8",
+        render_aggregator(&jobs, pagination.page(), total_pages),
+    )
+}
@@ -0,0 +1,344 @@
+use crate::handlers::jobs::{
+    LocationFields, build_job_input, parse_language, resolve_company_id, validate_expires_at,
+};
+use crate::models::category::Category;
+use crate::models::job::{Job, JobInput};
+use crate::services::moderation;
+use crate::utils::auth::get_user_from_token;
+use crate::utils::locale::DEFAULT_LANGUAGE;
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::header::CONTENT_TYPE,
+    web::{Bytes, Data, Query},
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{PgPool, types::Decimal};
+use std::collections::{HashMap, HashSet};
+
+const MAX_IMPORT_ROWS: usize = 1000;
+const MAX_EXTERNAL_REF_LENGTH: usize = 100;
+/// Upper bound on the upload size, enough for the row limit with long
+/// descriptions.
+pub const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    pub company_id: Option<i32>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// One posting in an upload. CSV files use these names as their header row
+/// and leave optional columns empty.
+#[derive(Deserialize)]
+pub struct ImportRow {
+    pub external_ref: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub salary: Decimal,
+    pub category_id: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    /// The locale of the title and description, English if omitted.
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ImportRowResult {
+    /// 1-based position in the upload, not counting a CSV header.
+    pub row: usize,
+    pub external_ref: Option<String>,
+    /// `created`, `updated` or `unchanged`; in a dry run, what would happen.
+    pub action: &'static str,
+    pub job_id: Option<i32>,
+}
+
+#[derive(Serialize)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub external_ref: Option<String>,
+    pub error: String,
+}
+
+#[derive(Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub rows: Vec<ImportRowResult>,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// Parses the body as CSV or as a JSON array, depending on the content type.
+/// Rows that fail to parse are reported individually rather than failing the
+/// whole upload.
+fn parse_rows(
+    content_type: &str,
+    body: &[u8],
+) -> Result<Vec<Result<ImportRow, String>>, HttpResponse> {
+    if content_type.starts_with("text/csv") {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(body);
+        if let Err(error) = reader.headers() {
+            return Err(HttpResponse::BadRequest().json(format!("Invalid CSV header: {error}")));
+        }
+        Ok(reader
+            .deserialize::<ImportRow>()
+            .map(|row| row.map_err(|error| error.to_string()))
+            .collect())
+    } else if content_type.starts_with("application/json") {
+        let rows: Vec<Value> = match serde_json::from_slice(body) {
+            Ok(rows) => rows,
+            Err(_) => {
+                return Err(HttpResponse::BadRequest().json("Expected a JSON array of jobs"));
+            }
+        };
+        Ok(rows
+            .into_iter()
+            .map(|row| serde_json::from_value(row).map_err(|error| error.to_string()))
+            .collect())
+    } else {
+        Err(HttpResponse::UnsupportedMediaType().json("Upload text/csv or application/json"))
+    }
+}
+
+/// Applies the same checks as `POST /api/jobs` to one row.
+async fn validate_row(
+    pool: &PgPool,
+    categories: &mut HashMap<i32, bool>,
+    row: &ImportRow,
+) -> Result<JobInput, String> {
+    if let Some(external_ref) = &row.external_ref
+        && (external_ref.is_empty() || external_ref.len() > MAX_EXTERNAL_REF_LENGTH)
+    {
+        return Err(format!(
+            "external_ref must be 1 to {MAX_EXTERNAL_REF_LENGTH} characters"
+        ));
+    }
+
+    let category_exists = match categories.get(&row.category_id) {
+        Some(exists) => *exists,
+        None => {
+            let exists = Category::find_by_id(pool, &row.category_id).await.is_ok();
+            categories.insert(row.category_id, exists);
+            exists
+        }
+    };
+    if !category_exists {
+        return Err("Category not found".to_string());
+    }
+
+    validate_expires_at(row.expires_at)?;
+    let location_fields = LocationFields {
+        country: row.country.clone(),
+        region: row.region.clone(),
+        city: row.city.clone(),
+        postal_code: row.postal_code.clone(),
+        latitude: row.latitude,
+        longitude: row.longitude,
+    };
+    let mut input = build_job_input(
+        &row.title,
+        &row.description,
+        &row.location,
+        &location_fields,
+        row.salary,
+        row.category_id,
+        row.expires_at,
+    )?;
+    input.language = parse_language(row.language.as_deref(), DEFAULT_LANGUAGE)?;
+    Ok(input)
+}
+
+fn same_content(job: &Job, input: &JobInput) -> bool {
+    job.title == input.title
+        && job.description == input.description
+        && job.language == input.language
+        && job.location == input.location
+        && job.country == input.country
+        && job.region == input.region
+        && job.city == input.city
+        && job.postal_code == input.postal_code
+        && job.latitude == input.latitude
+        && job.longitude == input.longitude
+        && job.salary == input.salary
+        && job.category_id == input.category_id
+        && job.expires_at == input.expires_at
+}
+
+/// Creates or updates postings in bulk. Every row is validated and errors are
+/// reported per row; the valid rows are written in one transaction. Rows with
+/// an `external_ref` update the company's job with that reference if there is
+/// one, so uploading the same file twice leaves the second run unchanged.
+pub async fn import_jobs(
+    pool: Data<PgPool>,
+    query: Query<ImportQuery>,
+    body: Bytes,
+    token: String,
+    req: HttpRequest,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let company_id = match resolve_company_id(&pool, &user, query.company_id).await {
+        Ok(company_id) => company_id,
+        Err(response) => return response,
+    };
+
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    let rows = match parse_rows(content_type, &body) {
+        Ok(rows) => rows,
+        Err(response) => return response,
+    };
+    if rows.is_empty() {
+        return HttpResponse::BadRequest().json("The upload contains no jobs");
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return HttpResponse::BadRequest().json(format!(
+            "An import can contain at most {MAX_IMPORT_ROWS} jobs"
+        ));
+    }
+
+    let mut report = ImportReport {
+        dry_run: query.dry_run,
+        created: 0,
+        updated: 0,
+        unchanged: 0,
+        rows: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let mut categories = HashMap::new();
+    let mut seen_refs = HashSet::new();
+    let mut valid = Vec::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+        let mut row = match row {
+            Ok(row) => row,
+            Err(error) => {
+                report.errors.push(ImportRowError {
+                    row: row_number,
+                    external_ref: None,
+                    error,
+                });
+                continue;
+            }
+        };
+        row.external_ref = row
+            .external_ref
+            .map(|external_ref| external_ref.trim().to_string());
+        let external_ref = row.external_ref.clone();
+
+        let result = match &external_ref {
+            Some(external_ref) if !seen_refs.insert(external_ref.clone()) => {
+                Err("external_ref appears more than once in this upload".to_string())
+            }
+            _ => validate_row(&pool, &mut categories, &row).await,
+        };
+        match result {
+            Ok(input) => valid.push((row_number, external_ref, input)),
+            Err(error) => report.errors.push(ImportRowError {
+                row: row_number,
+                external_ref,
+                error,
+            }),
+        }
+    }
+
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to import jobs"),
+    };
+
+    for (row_number, external_ref, input) in valid {
+        let existing = match &external_ref {
+            Some(external_ref) => {
+                match Job::find_by_external_ref(&mut tx, &company_id, external_ref).await {
+                    Ok(existing) => existing,
+                    Err(_) => {
+                        return HttpResponse::InternalServerError().json("Failed to import jobs");
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let (action, job_id) = match existing {
+            Some((_, true)) => {
+                report.errors.push(ImportRowError {
+                    row: row_number,
+                    external_ref,
+                    error: "The job with this external_ref was deleted; restore it first"
+                        .to_string(),
+                });
+                continue;
+            }
+            Some((job, false)) if same_content(&job, &input) => ("unchanged", Some(job.id)),
+            Some((job, false)) if query.dry_run => ("updated", Some(job.id)),
+            Some((job, false)) => {
+                let flags = moderation::check(&input.title, &input.description);
+                match Job::update_in(&mut tx, &job.id, &input, None, &user.id, &flags).await {
+                    Ok(_) => ("updated", Some(job.id)),
+                    Err(_) => {
+                        return HttpResponse::InternalServerError().json("Failed to import jobs");
+                    }
+                }
+            }
+            None if query.dry_run => ("created", None),
+            None => {
+                let flags = moderation::check(&input.title, &input.description);
+                match Job::create_in(
+                    &mut tx,
+                    &user.id,
+                    &company_id,
+                    &input,
+                    external_ref.as_deref(),
+                    &flags,
+                )
+                .await
+                {
+                    Ok(job) => ("created", Some(job.id)),
+                    Err(_) => {
+                        return HttpResponse::InternalServerError().json("Failed to import jobs");
+                    }
+                }
+            }
+        };
+
+        match action {
+            "created" => report.created += 1,
+            "updated" => report.updated += 1,
+            _ => report.unchanged += 1,
+        }
+        report.rows.push(ImportRowResult {
+            row: row_number,
+            external_ref,
+            action,
+            job_id,
+        });
+    }
+
+    if !query.dry_run && tx.commit().await.is_err() {
+        return HttpResponse::InternalServerError().json("Failed to import jobs");
+    }
+
+    report.errors.sort_by_key(|error| error.row);
+    HttpResponse::Ok().json(report)
+}
@@ -0,0 +1,310 @@
+use crate::models::job::Job;
+use crate::models::job_question::{ApplicationAnswer, JobQuestion, JobQuestionInput};
+use crate::utils::auth::{can_edit_job, get_user_from_token};
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path},
+};
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+const QUESTION_KINDS: [&str; 4] = ["yes_no", "multiple_choice", "numeric", "short_text"];
+const DEFAULT_MAX_LENGTH: i32 = 500;
+const MAX_QUESTIONS: usize = 20;
+
+#[derive(Deserialize)]
+pub struct QuestionRequest {
+    /// Id of an existing question to update; omit to add a new one.
+    pub id: Option<i32>,
+    pub prompt: String,
+    pub kind: String,
+    #[serde(default = "default_required")]
+    pub required: bool,
+    #[serde(default)]
+    pub options: Vec<String>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub max_length: Option<i32>,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+pub struct UpdateJobQuestionsRequest {
+    pub questions: Vec<QuestionRequest>,
+}
+
+#[derive(Deserialize)]
+pub struct AnswerRequest {
+    pub question_id: i32,
+    /// `true`/`false` or `"yes"`/`"no"` for yes/no questions, a number for
+    /// numeric ones and a string otherwise.
+    pub answer: Value,
+}
+
+fn question_input(question: &QuestionRequest) -> Result<JobQuestionInput, String> {
+    let prompt = question.prompt.trim();
+    if prompt.is_empty() {
+        return Err("Question prompt must not be empty".to_string());
+    }
+    if !QUESTION_KINDS.contains(&question.kind.as_str()) {
+        return Err(format!(
+            "Question kind must be one of: {}",
+            QUESTION_KINDS.join(", ")
+        ));
+    }
+
+    let options: Vec<String> = question
+        .options
+        .iter()
+        .map(|option| option.trim().to_string())
+        .collect();
+    if question.kind == "multiple_choice" {
+        let mut unique = options.clone();
+        unique.sort();
+        unique.dedup();
+        if unique.len() < 2 || unique.len() != options.len() || unique[0].is_empty() {
+            return Err(format!(
+                "\"{prompt}\" needs at least two distinct, non-empty options"
+            ));
+        }
+    } else if !options.is_empty() {
+        return Err(format!(
+            "Only multiple choice questions take options (\"{prompt}\")"
+        ));
+    }
+
+    if question.kind != "numeric" && (question.min_value.is_some() || question.max_value.is_some())
+    {
+        return Err(format!(
+            "Only numeric questions take min_value and max_value (\"{prompt}\")"
+        ));
+    }
+    if let (Some(min), Some(max)) = (question.min_value, question.max_value)
+        && min > max
+    {
+        return Err(format!(
+            "min_value must not exceed max_value (\"{prompt}\")"
+        ));
+    }
+
+    let max_length = match question.kind.as_str() {
+        "short_text" => match question.max_length {
+            Some(max_length) if max_length < 1 => {
+                return Err(format!("max_length must be positive (\"{prompt}\")"));
+            }
+            max_length => Some(max_length.unwrap_or(DEFAULT_MAX_LENGTH)),
+        },
+        _ if question.max_length.is_some() => {
+            return Err(format!(
+                "Only short text questions take max_length (\"{prompt}\")"
+            ));
+        }
+        _ => None,
+    };
+
+    Ok(JobQuestionInput {
+        id: question.id,
+        prompt: prompt.to_string(),
+        kind: question.kind.clone(),
+        required: question.required,
+        options,
+        min_value: question.min_value,
+        max_value: question.max_value,
+        max_length,
+    })
+}
+
+/// Normalizes one answer to its stored text form, or `None` when the value
+/// counts as unanswered.
+fn normalize_answer(question: &JobQuestion, answer: &Value) -> Result<Option<String>, String> {
+    let prompt = &question.prompt;
+    match (question.kind.as_str(), answer) {
+        (_, Value::Null) => Ok(None),
+        ("yes_no", Value::Bool(yes)) => Ok(Some(if *yes { "yes" } else { "no" }.to_string())),
+        ("yes_no", Value::String(text)) => match text.trim().to_lowercase().as_str() {
+            "yes" => Ok(Some("yes".to_string())),
+            "no" => Ok(Some("no".to_string())),
+            _ => Err(format!("\"{prompt}\" must be answered yes or no")),
+        },
+        ("multiple_choice", Value::String(choice)) => {
+            if question
+                .options
+                .iter()
+                .any(|option| option == choice.trim())
+            {
+                Ok(Some(choice.trim().to_string()))
+            } else {
+                Err(format!(
+                    "\"{prompt}\" must be one of: {}",
+                    question.options.join(", ")
+                ))
+            }
+        }
+        ("numeric", Value::Number(number)) => {
+            let Some(value) = number.as_f64() else {
+                return Err(format!("\"{prompt}\" must be a number"));
+            };
+            if question.min_value.is_some_and(|min| value < min)
+                || question.max_value.is_some_and(|max| value > max)
+            {
+                return Err(format!("\"{prompt}\" is out of range"));
+            }
+            Ok(Some(number.to_string()))
+        }
+        ("short_text", Value::String(text)) => {
+            let text = text.trim();
+            let max_length = question.max_length.unwrap_or(DEFAULT_MAX_LENGTH) as usize;
+            if text.is_empty() {
+                Ok(None)
+            } else if text.chars().count() > max_length {
+                Err(format!(
+                    "\"{prompt}\" must be at most {max_length} characters"
+                ))
+            } else {
+                Ok(Some(text.to_string()))
+            }
+        }
+        _ => Err(format!("\"{prompt}\" has an answer of the wrong type")),
+    }
+}
+
+/// Checks answers against the job's questions: every answer must belong to
+/// one of them, at most once, with a value of the right shape, and every
+/// required question must be answered.
+pub fn validate_answers(
+    questions: &[JobQuestion],
+    answers: &[AnswerRequest],
+) -> Result<Vec<ApplicationAnswer>, HttpResponse> {
+    let mut given: HashMap<i32, &Value> = HashMap::new();
+    for answer in answers {
+        if !questions
+            .iter()
+            .any(|question| question.id == answer.question_id)
+        {
+            return Err(HttpResponse::BadRequest().json(format!(
+                "Question {} does not belong to this job",
+                answer.question_id
+            )));
+        }
+        if given.insert(answer.question_id, &answer.answer).is_some() {
+            return Err(HttpResponse::BadRequest().json(format!(
+                "Question {} is answered more than once",
+                answer.question_id
+            )));
+        }
+    }
+
+    let mut normalized = Vec::new();
+    for question in questions {
+        let answer = match given.get(&question.id) {
+            Some(value) => normalize_answer(question, value)
+                .map_err(|error| HttpResponse::BadRequest().json(error))?,
+            None => None,
+        };
+        match answer {
+            Some(answer) => normalized.push(ApplicationAnswer {
+                question_id: Some(question.id),
+                prompt: question.prompt.clone(),
+                kind: question.kind.clone(),
+                answer,
+            }),
+            None if question.required => {
+                return Err(
+                    HttpResponse::BadRequest().json(format!("\"{}\" is required", question.prompt))
+                );
+            }
+            None => {}
+        }
+    }
+
+    Ok(normalized)
+}
+
+pub async fn get_job_questions(pool: Data<PgPool>, job_id: Path<i32>) -> HttpResponse {
+    if Job::find_by_id(&pool, &job_id).await.is_err() {
+        return HttpResponse::NotFound().json("Job not found");
+    }
+
+    match JobQuestion::find_by_job_id(&pool, &job_id).await {
+        Ok(questions) => HttpResponse::Ok().json(questions),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get job questions"),
+    }
+}
+
+pub async fn update_job_questions(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    form: Json<UpdateJobQuestionsRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to update this job");
+    }
+
+    if form.questions.len() > MAX_QUESTIONS {
+        return HttpResponse::BadRequest()
+            .json(format!("A job can have at most {MAX_QUESTIONS} questions"));
+    }
+
+    let existing = match JobQuestion::find_by_job_id(&pool, &job_id).await {
+        Ok(questions) => questions,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json("Failed to update job questions");
+        }
+    };
+    let mut ids: Vec<i32> = form
+        .questions
+        .iter()
+        .filter_map(|question| question.id)
+        .collect();
+    if let Some(id) = ids
+        .iter()
+        .find(|id| !existing.iter().any(|question| question.id == **id))
+    {
+        return HttpResponse::BadRequest()
+            .json(format!("Question {id} does not belong to this job"));
+    }
+    let count = ids.len();
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.len() != count {
+        return HttpResponse::BadRequest().json("Each question id may only appear once");
+    }
+
+    let questions = match form
+        .questions
+        .iter()
+        .map(question_input)
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(questions) => questions,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+
+    if JobQuestion::set_for_job(&pool, &job_id, &questions)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to update job questions");
+    }
+
+    match JobQuestion::find_by_job_id(&pool, &job_id).await {
+        Ok(questions) => HttpResponse::Ok().json(questions),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get job questions"),
+    }
+}
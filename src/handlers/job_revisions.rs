@@ -0,0 +1,219 @@
+use crate::models::category::Category;
+use crate::models::job::{Job, JobInput};
+use crate::models::job_revision::JobRevision;
+use crate::models::user::User;
+use crate::services::moderation;
+use crate::utils::auth::{can_edit_job, get_user_from_token};
+use crate::utils::etag::{etag, if_match_version, precondition_failed};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::header::ETAG,
+    web::{Data, Path, Query},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+
+/// The fields a revision snapshots, in the order diffs list them.
+const SNAPSHOT_FIELDS: [&str; 12] = [
+    "title",
+    "description",
+    "location",
+    "country",
+    "region",
+    "city",
+    "postal_code",
+    "latitude",
+    "longitude",
+    "salary",
+    "category_id",
+    "expires_at",
+];
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Serialize)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub from: Value,
+    pub to: Value,
+}
+
+#[derive(Serialize)]
+pub struct RevisionDiff {
+    pub from: i32,
+    pub to: i32,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Loads the job, failing unless `user` may edit it. Revision history is
+/// only shown to the people who can change the posting.
+async fn find_editable_job(pool: &PgPool, job_id: &i32, user: &User) -> Result<Job, HttpResponse> {
+    let job = match Job::find_by_id(pool, job_id).await {
+        Ok(job) => job,
+        Err(_) => return Err(HttpResponse::NotFound().json("Job not found")),
+    };
+
+    if !can_edit_job(pool, user, &job).await {
+        return Err(
+            HttpResponse::Forbidden().json("You do not have permission to view this job's history")
+        );
+    }
+
+    Ok(job)
+}
+
+fn diff_revisions(from: &JobRevision, to: &JobRevision) -> Vec<FieldChange> {
+    let (Ok(Value::Object(mut from)), Ok(Value::Object(mut to))) =
+        (serde_json::to_value(from), serde_json::to_value(to))
+    else {
+        return Vec::new();
+    };
+
+    SNAPSHOT_FIELDS
+        .iter()
+        .filter_map(|&field| {
+            let from = from.remove(field).unwrap_or(Value::Null);
+            let to = to.remove(field).unwrap_or(Value::Null);
+            (from != to).then_some(FieldChange { field, from, to })
+        })
+        .collect()
+}
+
+pub async fn get_job_revisions(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = find_editable_job(&pool, &job_id, &user).await {
+        return response;
+    }
+
+    match JobRevision::find_by_job_id(&pool, &job_id).await {
+        Ok(revisions) => HttpResponse::Ok().json(revisions),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get job revisions"),
+    }
+}
+
+pub async fn get_job_revision(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    token: String,
+) -> HttpResponse {
+    let (job_id, version) = path.into_inner();
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = find_editable_job(&pool, &job_id, &user).await {
+        return response;
+    }
+
+    match JobRevision::find_by_version(&pool, &job_id, &version).await {
+        Ok(revision) => HttpResponse::Ok().json(revision),
+        Err(_) => HttpResponse::NotFound().json("Revision not found"),
+    }
+}
+
+pub async fn get_job_revision_diff(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    query: Query<DiffQuery>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = find_editable_job(&pool, &job_id, &user).await {
+        return response;
+    }
+
+    let from = match JobRevision::find_by_version(&pool, &job_id, &query.from).await {
+        Ok(revision) => revision,
+        Err(_) => return HttpResponse::NotFound().json("Revision not found"),
+    };
+    let to = match JobRevision::find_by_version(&pool, &job_id, &query.to).await {
+        Ok(revision) => revision,
+        Err(_) => return HttpResponse::NotFound().json("Revision not found"),
+    };
+
+    HttpResponse::Ok().json(RevisionDiff {
+        from: from.version,
+        to: to.version,
+        changes: diff_revisions(&from, &to),
+    })
+}
+
+/// Writes an old revision's content back as a new revision. The expiry date
+/// is scheduling rather than content, so the current one is kept.
+pub async fn restore_job_revision(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    token: String,
+    req: HttpRequest,
+) -> HttpResponse {
+    let (job_id, version) = path.into_inner();
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+
+    let job = match find_editable_job(&pool, &job_id, &user).await {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+
+    let revision = match JobRevision::find_by_version(&pool, &job_id, &version).await {
+        Ok(revision) => revision,
+        Err(_) => return HttpResponse::NotFound().json("Revision not found"),
+    };
+
+    if Category::find_by_id(&pool, &revision.category_id)
+        .await
+        .is_err()
+    {
+        return HttpResponse::BadRequest().json("The revision's category no longer exists");
+    }
+
+    let input = JobInput {
+        title: revision.title,
+        description: revision.description,
+        language: job.language.clone(),
+        location: revision.location,
+        country: revision.country,
+        region: revision.region,
+        city: revision.city,
+        postal_code: revision.postal_code,
+        latitude: revision.latitude,
+        longitude: revision.longitude,
+        salary: revision.salary,
+        category_id: revision.category_id,
+        expires_at: job.expires_at,
+    };
+
+    let flags = moderation::check(&input.title, &input.description);
+    match Job::update(&pool, &job_id, &input, expected_version, &user.id, &flags).await {
+        Ok(Some(job)) => HttpResponse::Ok()
+            .insert_header((ETAG, etag(job.version)))
+            .json(job),
+        Ok(None) => precondition_failed(),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to restore revision"),
+    }
+}
@@ -0,0 +1,269 @@
+use crate::handlers::companies::{MEMBER_ROLES, require_member_role};
+use crate::handlers::jobs::{LocationFields, build_job_input, parse_language};
+use crate::models::category::Category;
+use crate::models::job::Job;
+use crate::models::job_template::{JobTemplate, JobTemplateInput};
+use crate::services::templates::{placeholders, render};
+use crate::utils::auth::get_user_from_token;
+use crate::utils::locale::DEFAULT_LANGUAGE;
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, types::Decimal};
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+pub struct JobTemplateRequest {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub salary: Decimal,
+    pub category_id: i32,
+    pub language: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct InstantiateTemplateRequest {
+    /// Values for the template's placeholders, by name.
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+}
+
+#[derive(Serialize)]
+pub struct JobTemplateResponse {
+    #[serde(flatten)]
+    pub template: JobTemplate,
+    /// The names that need a value when a draft is created from the template.
+    pub placeholders: Vec<String>,
+}
+
+impl From<JobTemplate> for JobTemplateResponse {
+    fn from(template: JobTemplate) -> Self {
+        let placeholders =
+            placeholders(&[&template.title, &template.description, &template.location])
+                .unwrap_or_default();
+        JobTemplateResponse {
+            template,
+            placeholders,
+        }
+    }
+}
+
+async fn template_input(
+    pool: &PgPool,
+    form: &JobTemplateRequest,
+) -> Result<JobTemplateInput, HttpResponse> {
+    if form.name.trim().is_empty() || form.title.trim().is_empty() {
+        return Err(HttpResponse::BadRequest().json("Template name and title must not be empty"));
+    }
+    if let Err(error) = placeholders(&[&form.title, &form.description, &form.location]) {
+        return Err(HttpResponse::BadRequest().json(error));
+    }
+    if Category::find_by_id(pool, &form.category_id).await.is_err() {
+        return Err(HttpResponse::BadRequest().json("Category not found"));
+    }
+    let language = parse_language(form.language.as_deref(), DEFAULT_LANGUAGE)
+        .map_err(|error| HttpResponse::BadRequest().json(error))?;
+
+    Ok(JobTemplateInput {
+        name: form.name.trim().to_string(),
+        title: form.title.clone(),
+        description: form.description.clone(),
+        location: form.location.clone(),
+        salary: form.salary,
+        category_id: form.category_id,
+        language,
+    })
+}
+
+/// Checks the caller belongs to the company and returns their user id.
+async fn require_member(pool: &PgPool, company_id: &i32, token: &str) -> Result<i32, HttpResponse> {
+    let user = get_user_from_token(pool, token).await?;
+    require_member_role(pool, company_id, &user.id, &MEMBER_ROLES).await?;
+    Ok(user.id)
+}
+
+pub async fn get_job_templates(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = require_member(&pool, &company_id, &token).await {
+        return response;
+    }
+
+    match JobTemplate::find_by_company_id(&pool, &company_id).await {
+        Ok(templates) => HttpResponse::Ok().json(
+            templates
+                .into_iter()
+                .map(JobTemplateResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get job templates"),
+    }
+}
+
+pub async fn get_job_template(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    token: String,
+) -> HttpResponse {
+    let (company_id, template_id) = path.into_inner();
+    if let Err(response) = require_member(&pool, &company_id, &token).await {
+        return response;
+    }
+
+    match JobTemplate::find_by_id(&pool, &company_id, &template_id).await {
+        Ok(template) => HttpResponse::Ok().json(JobTemplateResponse::from(template)),
+        Err(_) => HttpResponse::NotFound().json("Job template not found"),
+    }
+}
+
+pub async fn create_job_template(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    form: Json<JobTemplateRequest>,
+    token: String,
+) -> HttpResponse {
+    let user_id = match require_member(&pool, &company_id, &token).await {
+        Ok(user_id) => user_id,
+        Err(response) => return response,
+    };
+
+    let input = match template_input(&pool, &form).await {
+        Ok(input) => input,
+        Err(response) => return response,
+    };
+
+    match JobTemplate::create(&pool, &company_id, &input, &user_id).await {
+        Ok(template) => HttpResponse::Ok().json(JobTemplateResponse::from(template)),
+        Err(_) => HttpResponse::Conflict().json("A template with this name already exists"),
+    }
+}
+
+pub async fn update_job_template(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    form: Json<JobTemplateRequest>,
+    token: String,
+) -> HttpResponse {
+    let (company_id, template_id) = path.into_inner();
+    if let Err(response) = require_member(&pool, &company_id, &token).await {
+        return response;
+    }
+
+    if JobTemplate::find_by_id(&pool, &company_id, &template_id)
+        .await
+        .is_err()
+    {
+        return HttpResponse::NotFound().json("Job template not found");
+    }
+
+    let input = match template_input(&pool, &form).await {
+        Ok(input) => input,
+        Err(response) => return response,
+    };
+
+    match JobTemplate::update(&pool, &company_id, &template_id, &input).await {
+        Ok(template) => HttpResponse::Ok().json(JobTemplateResponse::from(template)),
+        Err(_) => HttpResponse::Conflict().json("A template with this name already exists"),
+    }
+}
+
+pub async fn delete_job_template(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    token: String,
+) -> HttpResponse {
+    let (company_id, template_id) = path.into_inner();
+    if let Err(response) = require_member(&pool, &company_id, &token).await {
+        return response;
+    }
+
+    match JobTemplate::delete(&pool, &company_id, &template_id).await {
+        Ok(true) => HttpResponse::Ok().json("Job template deleted"),
+        Ok(false) => HttpResponse::NotFound().json("Job template not found"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to delete job template"),
+    }
+}
+
+/// Creates a draft posting from the template with its placeholders filled in.
+pub async fn create_job_from_template(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    form: Json<InstantiateTemplateRequest>,
+    token: String,
+) -> HttpResponse {
+    let (company_id, template_id) = path.into_inner();
+    let user_id = match require_member(&pool, &company_id, &token).await {
+        Ok(user_id) => user_id,
+        Err(response) => return response,
+    };
+
+    let template = match JobTemplate::find_by_id(&pool, &company_id, &template_id).await {
+        Ok(template) => template,
+        Err(_) => return HttpResponse::NotFound().json("Job template not found"),
+    };
+
+    let mut missing = Vec::new();
+    let mut fill = |text: &str| {
+        render(text, &form.values).unwrap_or_else(|names| {
+            missing.extend(names);
+            String::new()
+        })
+    };
+    let title = fill(&template.title);
+    let description = fill(&template.description);
+    let location = fill(&template.location);
+    if !missing.is_empty() {
+        missing.sort();
+        missing.dedup();
+        return HttpResponse::BadRequest().json(format!(
+            "Missing values for placeholders: {}",
+            missing.join(", ")
+        ));
+    }
+    if title.trim().is_empty() {
+        return HttpResponse::BadRequest().json("Title must not be empty");
+    }
+
+    let no_location = LocationFields {
+        country: None,
+        region: None,
+        city: None,
+        postal_code: None,
+        latitude: None,
+        longitude: None,
+    };
+    let mut input = match build_job_input(
+        &title,
+        &description,
+        &location,
+        &no_location,
+        template.salary,
+        template.category_id,
+        None,
+    ) {
+        Ok(input) => input,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+    input.language = template.language;
+
+    match Job::create_draft(
+        &pool,
+        &user_id,
+        &company_id,
+        &input,
+        None,
+        Some(template.id),
+        None,
+    )
+    .await
+    {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to create job"),
+    }
+}
@@ -0,0 +1,121 @@
+use crate::handlers::jobs::can_view_unapproved;
+use crate::models::job::Job;
+use crate::models::job_translation::JobTranslation;
+use crate::services::moderation;
+use crate::utils::auth::{can_edit_job, get_user_from_token};
+use crate::utils::locale::normalize_locale;
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct TranslationRequest {
+    pub title: String,
+    pub description: String,
+}
+
+/// Loads the job and checks the caller may edit it.
+async fn find_editable_job(pool: &PgPool, job_id: &i32, token: &str) -> Result<Job, HttpResponse> {
+    let user = get_user_from_token(pool, token).await?;
+
+    let job = match Job::find_by_id(pool, job_id).await {
+        Ok(job) => job,
+        Err(_) => return Err(HttpResponse::NotFound().json("Job not found")),
+    };
+
+    if !can_edit_job(pool, &user, &job).await {
+        return Err(
+            HttpResponse::Forbidden().json("You do not have permission to translate this job")
+        );
+    }
+
+    Ok(job)
+}
+
+fn parse_locale(locale: &str) -> Result<String, HttpResponse> {
+    normalize_locale(locale).ok_or_else(|| {
+        HttpResponse::BadRequest().json("locale must be a language tag such as en or pt-BR")
+    })
+}
+
+pub async fn get_job_translations(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    token: Option<String>,
+) -> HttpResponse {
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if job.moderation_status != "approved" && !can_view_unapproved(&pool, token, &job).await {
+        return HttpResponse::NotFound().json("Job not found");
+    }
+
+    match JobTranslation::find_by_job_id(&pool, &job.id).await {
+        Ok(translations) => HttpResponse::Ok().json(translations),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get translations"),
+    }
+}
+
+/// Adds or replaces the translation for one locale. Translations pass the
+/// same automatic checks as the posting itself.
+pub async fn put_job_translation(
+    pool: Data<PgPool>,
+    path: Path<(i32, String)>,
+    form: Json<TranslationRequest>,
+    token: String,
+) -> HttpResponse {
+    let (job_id, locale) = path.into_inner();
+    let job = match find_editable_job(&pool, &job_id, &token).await {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+
+    let locale = match parse_locale(&locale) {
+        Ok(locale) => locale,
+        Err(response) => return response,
+    };
+    if locale == job.language {
+        return HttpResponse::Conflict()
+            .json("The job is already written in this language; update the job instead");
+    }
+
+    let title = form.title.trim();
+    let description = form.description.trim();
+    if title.is_empty() || description.is_empty() {
+        return HttpResponse::BadRequest().json("Title and description must not be empty");
+    }
+
+    let flags = moderation::check(title, description);
+    match JobTranslation::upsert(&pool, &job.id, &locale, title, description, &flags).await {
+        Ok(translation) => HttpResponse::Ok().json(translation),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to save translation"),
+    }
+}
+
+pub async fn delete_job_translation(
+    pool: Data<PgPool>,
+    path: Path<(i32, String)>,
+    token: String,
+) -> HttpResponse {
+    let (job_id, locale) = path.into_inner();
+    let job = match find_editable_job(&pool, &job_id, &token).await {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+
+    let locale = match parse_locale(&locale) {
+        Ok(locale) => locale,
+        Err(response) => return response,
+    };
+
+    match JobTranslation::delete(&pool, &job.id, &locale).await {
+        Ok(true) => HttpResponse::Ok().json("Translation deleted"),
+        Ok(false) => HttpResponse::NotFound().json("Translation not found"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to delete translation"),
+    }
+}
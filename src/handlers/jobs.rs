@@ -1,19 +1,73 @@
-use crate::models::job::Job;
-use crate::utils::auth::get_user_from_token;
+use crate::models::category::Category;
+use crate::models::company::{Company, CompanyMember};
+use crate::models::featured_placement::FeaturedPlacement;
+use crate::models::job::{EXTENSION_DAYS, Job, JobInput, JobSearch};
+use crate::models::job_event::{DailyJobStats, JobEvent};
+use crate::models::job_translation::JobTranslation;
+use crate::models::notification::Notification;
+use crate::models::saved_job::SavedJob;
+use crate::models::user::User;
+use crate::services::analytics::AnalyticsRecorder;
+use crate::services::duplicates::{
+    DuplicateMatch, DuplicatePolicy, find_clusters, find_duplicates,
+};
+use crate::services::featured::mix_featured;
+use crate::services::feeds::escape_xml;
+use crate::services::geocoding::{geocode, geocode_location};
+use crate::services::moderation;
+use crate::services::recommendations::{Recommendation, recommend};
+use crate::services::similar::SimilarJobsCache;
+use crate::services::translations::localize;
+use crate::utils::auth::{can_delete_job, can_edit_job, get_admin_from_token, get_user_from_token};
+use crate::utils::etag::{etag, if_match_version, precondition_failed};
+use crate::utils::locale::{DEFAULT_LANGUAGE, normalize_locale, requested_locales};
+use crate::utils::pagination::{Page, Pagination};
 use actix_web::{
-    HttpResponse,
-    web::{Data, Json, Path},
+    HttpRequest, HttpResponse,
+    http::header::{CONTENT_LANGUAGE, ETAG, VARY},
+    web::{Data, Json, Path, Query},
 };
-use serde::Deserialize;
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::{PgPool, types::Decimal};
+use std::collections::HashMap;
+
+const DEFAULT_RADIUS_KM: f64 = 25.0;
+const DEFAULT_STATS_DAYS: i64 = 30;
+const MAX_STATS_DAYS: i64 = 365;
+const MAX_LIFETIME_DAYS: i64 = 365;
+const DEFAULT_SIMILAR_LIMIT: i64 = 5;
+const MAX_SIMILAR_LIMIT: i64 = 20;
+
+#[derive(Deserialize)]
+pub struct LocationFields {
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
 
 #[derive(Deserialize)]
 pub struct CreateJobRequest {
+    pub company_id: Option<i32>,
     pub title: String,
     pub description: String,
     pub location: String,
+    #[serde(flatten)]
+    pub location_fields: LocationFields,
     pub salary: Decimal,
-    pub category: String,
+    pub category_id: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    /// The locale of the title and description, English if omitted.
+    pub language: Option<String>,
+    /// Saves the posting as a draft that goes live at this time instead of
+    /// publishing it now.
+    pub publish_at: Option<NaiveDateTime>,
+    /// The IANA timezone `publish_at` is given in, UTC if omitted.
+    pub timezone: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -21,8 +75,332 @@ pub struct UpdateJobRequest {
     pub title: String,
     pub description: String,
     pub location: String,
+    #[serde(flatten)]
+    pub location_fields: LocationFields,
     pub salary: Decimal,
-    pub category: String,
+    pub category_id: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    /// Omit to keep the current language.
+    pub language: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PatchJobRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    #[serde(flatten)]
+    pub location_fields: LocationFields,
+    pub salary: Option<Decimal>,
+    pub category_id: Option<i32>,
+    /// `null` clears the expiry; leaving the field out keeps it.
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub expires_at: Option<Option<NaiveDateTime>>,
+    /// Omit to keep the current language.
+    pub language: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ScheduleJobRequest {
+    /// `null` unschedules the draft.
+    pub publish_at: Option<NaiveDateTime>,
+    /// The IANA timezone `publish_at` is given in, UTC if omitted.
+    pub timezone: Option<String>,
+    /// Closes the posting this many days after it goes live. Without it the
+    /// draft keeps its current expiry.
+    pub expires_after_days: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct SimilarJobsQuery {
+    pub limit: Option<i64>,
+    /// Leaves out other postings of the same company.
+    #[serde(default)]
+    pub exclude_same_employer: bool,
+}
+
+#[derive(Serialize)]
+pub struct SimilarJob {
+    #[serde(flatten)]
+    pub job: Job,
+    pub score: f64,
+}
+
+#[derive(Deserialize)]
+pub struct StatsQuery {
+    pub days: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct JobStats {
+    pub job_id: i32,
+    pub views: i64,
+    pub apply_clicks: i64,
+    pub applications: i64,
+    /// Applications per unique daily view.
+    pub conversion_rate: f64,
+    pub featured_impressions: i64,
+    pub featured_clicks: i64,
+    pub daily: Vec<DailyJobStats>,
+}
+
+#[derive(Serialize)]
+pub struct JobListing {
+    #[serde(flatten)]
+    pub job: Job,
+    /// Whether the signed-in seeker has saved this job.
+    pub saved: bool,
+    /// Whether the job is in a paid featured slot rather than an organic one.
+    pub featured: bool,
+}
+
+#[derive(Serialize)]
+pub struct CheckedJob {
+    #[serde(flatten)]
+    pub job: Job,
+    /// Open jobs of the same company this posting appears to repeat.
+    pub duplicates: Vec<DuplicateMatch>,
+}
+
+#[derive(Deserialize)]
+pub struct JobFilter {
+    pub q: Option<String>,
+    pub category_id: Option<i32>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub min_salary: Option<Decimal>,
+    pub near: Option<String>,
+    pub radius_km: Option<f64>,
+}
+
+/// Wraps any value that is present, including `null`, in `Some`, so that a
+/// missing field (`None`) can be told apart from an explicit `null`.
+fn deserialize_present<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Validates listing filters and turns them into a model search. Saved
+/// searches go through here too, so both accept exactly the same filters.
+pub fn job_search(filter: &JobFilter) -> Result<JobSearch, HttpResponse> {
+    let mut search = JobSearch {
+        q: filter.q.clone().filter(|q| !q.trim().is_empty()),
+        category_id: filter.category_id,
+        city: filter.city.clone(),
+        country: filter.country.clone(),
+        min_salary: filter.min_salary,
+        ..JobSearch::default()
+    };
+
+    if let Some(near) = &filter.near {
+        let Some((latitude, longitude)) = parse_near(near) else {
+            return Err(HttpResponse::BadRequest().json("near must be formatted as lat,lng"));
+        };
+        let radius_km = filter.radius_km.unwrap_or(DEFAULT_RADIUS_KM);
+        if radius_km <= 0.0 {
+            return Err(HttpResponse::BadRequest().json("radius_km must be positive"));
+        }
+        search.latitude = Some(latitude);
+        search.longitude = Some(longitude);
+        search.radius_km = Some(radius_km);
+    }
+
+    Ok(search)
+}
+
+/// Builds the model input for a posting, geocoding it from the bundled city
+/// dataset when the client did not send coordinates.
+pub fn build_job_input(
+    title: &str,
+    description: &str,
+    location: &str,
+    fields: &LocationFields,
+    salary: Decimal,
+    category_id: i32,
+    expires_at: Option<NaiveDateTime>,
+) -> Result<JobInput, &'static str> {
+    let mut input = JobInput {
+        title: title.to_string(),
+        description: description.to_string(),
+        language: DEFAULT_LANGUAGE.to_string(),
+        location: location.to_string(),
+        country: fields.country.clone(),
+        region: fields.region.clone(),
+        city: fields.city.clone(),
+        postal_code: fields.postal_code.clone(),
+        latitude: fields.latitude,
+        longitude: fields.longitude,
+        salary,
+        category_id,
+        expires_at,
+    };
+
+    match (input.latitude, input.longitude) {
+        (Some(latitude), Some(longitude)) => {
+            if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+                return Err("Coordinates are out of range");
+            }
+        }
+        (None, None) => {
+            let city = match &input.city {
+                Some(city) => geocode(city, input.country.as_deref()),
+                None => geocode_location(&input.location),
+            };
+            if let Some(city) = city {
+                input.latitude = Some(city.latitude);
+                input.longitude = Some(city.longitude);
+                input.city.get_or_insert_with(|| city.name.clone());
+                input.region.get_or_insert_with(|| city.region.clone());
+                input.country.get_or_insert_with(|| city.country.clone());
+            }
+        }
+        _ => {
+            return Err("Latitude and longitude must be provided together");
+        }
+    }
+
+    Ok(input)
+}
+
+/// Normalizes the requested language, or returns `fallback` when none was given.
+pub fn parse_language(language: Option<&str>, fallback: &str) -> Result<String, &'static str> {
+    match language {
+        Some(language) => {
+            normalize_locale(language).ok_or("language must be a language tag such as en or pt-BR")
+        }
+        None => Ok(fallback.to_string()),
+    }
+}
+
+/// A posting cannot switch to a language it already has a translation in.
+async fn check_language_change(
+    pool: &PgPool,
+    job: &Job,
+    language: &str,
+) -> Result<(), HttpResponse> {
+    if job.language == language {
+        return Ok(());
+    }
+    match JobTranslation::find_by_job_id(pool, &job.id).await {
+        Ok(translations)
+            if translations
+                .iter()
+                .any(|translation| translation.locale == language) =>
+        {
+            Err(HttpResponse::Conflict()
+                .json("The job has a translation in this language; delete it first"))
+        }
+        Ok(_) => Ok(()),
+        Err(_) => Err(HttpResponse::InternalServerError().json("Failed to update job")),
+    }
+}
+
+pub fn validate_expires_at(expires_at: Option<NaiveDateTime>) -> Result<(), &'static str> {
+    if expires_at.is_some_and(|expires_at| expires_at <= Utc::now().naive_utc()) {
+        return Err("expires_at must be in the future");
+    }
+    Ok(())
+}
+
+/// Converts a wall-clock publication time in `timezone` to UTC. A time that
+/// a DST change skips is rejected; one it repeats resolves to the earlier.
+pub fn resolve_publish_at(
+    publish_at: NaiveDateTime,
+    timezone: Option<&str>,
+) -> Result<NaiveDateTime, &'static str> {
+    let publish_at = match timezone {
+        Some(timezone) => {
+            let timezone: Tz = timezone
+                .parse()
+                .map_err(|_| "timezone must be an IANA name such as Europe/Berlin")?;
+            timezone
+                .from_local_datetime(&publish_at)
+                .earliest()
+                .ok_or("publish_at does not exist in this timezone")?
+                .naive_utc()
+        }
+        None => publish_at,
+    };
+    if publish_at <= Utc::now().naive_utc() {
+        return Err("publish_at must be in the future");
+    }
+    Ok(publish_at)
+}
+
+fn parse_near(near: &str) -> Option<(f64, f64)> {
+    let (latitude, longitude) = near.split_once(',')?;
+    let latitude: f64 = latitude.trim().parse().ok()?;
+    let longitude: f64 = longitude.trim().parse().ok()?;
+    if (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude) {
+        Some((latitude, longitude))
+    } else {
+        None
+    }
+}
+
+/// Looks for near-duplicates of the posting among the company's open jobs.
+/// Under the `block` policy any match fails the request instead.
+async fn check_duplicates(
+    pool: &PgPool,
+    company_id: &i32,
+    job_id: Option<i32>,
+    input: &JobInput,
+) -> Result<Vec<DuplicateMatch>, HttpResponse> {
+    let policy = DuplicatePolicy::from_env();
+    if policy == DuplicatePolicy::Off {
+        return Ok(Vec::new());
+    }
+
+    let duplicates = match find_duplicates(pool, company_id, job_id, input).await {
+        Ok(duplicates) => duplicates,
+        Err(_) => {
+            return Err(
+                HttpResponse::InternalServerError().json("Failed to check for duplicate postings")
+            );
+        }
+    };
+
+    if policy == DuplicatePolicy::Block && !duplicates.is_empty() {
+        let ids: Vec<String> = duplicates
+            .iter()
+            .map(|duplicate| duplicate.job_id.to_string())
+            .collect();
+        return Err(HttpResponse::Conflict()
+            .json(format!("This posting duplicates job {}", ids.join(", "))));
+    }
+
+    Ok(duplicates)
+}
+
+/// The company a new posting belongs to: `company_id` if the user is a
+/// member, otherwise the user's only company.
+pub async fn resolve_company_id(
+    pool: &PgPool,
+    user: &User,
+    company_id: Option<i32>,
+) -> Result<i32, HttpResponse> {
+    match company_id {
+        Some(company_id) => match CompanyMember::find_role(pool, &company_id, &user.id).await {
+            Ok(Some(_)) => Ok(company_id),
+            Ok(None) => Err(HttpResponse::Forbidden().json("You are not a member of this company")),
+            Err(_) => Err(HttpResponse::InternalServerError().json("Failed to create job")),
+        },
+        None => {
+            match Company::find_by_user_id(pool, &user.id).await {
+                Ok(companies) => match companies.as_slice() {
+                    [company] => Ok(company.id),
+                    [] => Err(HttpResponse::BadRequest()
+                        .json("You must belong to a company to post jobs")),
+                    _ => Err(HttpResponse::BadRequest()
+                        .json("company_id is required when you belong to several companies")),
+                },
+                Err(_) => Err(HttpResponse::InternalServerError().json("Failed to create job")),
+            }
+        }
+    }
 }
 
 pub async fn create_job(
@@ -35,34 +413,351 @@ pub async fn create_job(
         Err(response) => return response,
     };
 
-    match Job::create(
-        &pool,
-        &user.id,
+    let company_id = match resolve_company_id(&pool, &user, form.company_id).await {
+        Ok(company_id) => company_id,
+        Err(response) => return response,
+    };
+
+    if Category::find_by_id(&pool, &form.category_id)
+        .await
+        .is_err()
+    {
+        return HttpResponse::BadRequest().json("Category not found");
+    }
+
+    if let Err(error) = validate_expires_at(form.expires_at) {
+        return HttpResponse::BadRequest().json(error);
+    }
+
+    let publish_at = match form.publish_at {
+        Some(publish_at) => match resolve_publish_at(publish_at, form.timezone.as_deref()) {
+            Ok(publish_at) => Some(publish_at),
+            Err(error) => return HttpResponse::BadRequest().json(error),
+        },
+        None => None,
+    };
+    if let (Some(publish_at), Some(expires_at)) = (publish_at, form.expires_at)
+        && expires_at <= publish_at
+    {
+        return HttpResponse::BadRequest().json("expires_at must be after publish_at");
+    }
+
+    let mut input = match build_job_input(
         &form.title,
         &form.description,
         &form.location,
+        &form.location_fields,
         form.salary,
-        &form.category,
-    )
-    .await
-    {
-        Ok(job) => HttpResponse::Ok().json(job),
+        form.category_id,
+        form.expires_at,
+    ) {
+        Ok(input) => input,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+    input.language = match parse_language(form.language.as_deref(), DEFAULT_LANGUAGE) {
+        Ok(language) => language,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+
+    let duplicates = match check_duplicates(&pool, &company_id, None, &input).await {
+        Ok(duplicates) => duplicates,
+        Err(response) => return response,
+    };
+
+    let job = match publish_at {
+        Some(publish_at) => {
+            Job::create_draft(
+                &pool,
+                &user.id,
+                &company_id,
+                &input,
+                None,
+                None,
+                Some(publish_at),
+            )
+            .await
+        }
+        None => {
+            let flags = moderation::check(&input.title, &input.description);
+            Job::create(&pool, &user.id, &company_id, &input, &flags).await
+        }
+    };
+    match job {
+        Ok(job) => HttpResponse::Ok().json(CheckedJob { job, duplicates }),
         Err(_) => HttpResponse::InternalServerError().json("Failed to create job"),
     }
 }
 
-pub async fn get_job_by_id(pool: Data<PgPool>, job_id: Path<i32>) -> HttpResponse {
+/// Whether the caller may see a posting that has not passed moderation: only
+/// the company's members and admins can.
+pub async fn can_view_unapproved(pool: &PgPool, token: Option<String>, job: &Job) -> bool {
+    let Some(token) = token else {
+        return false;
+    };
+    match get_user_from_token(pool, &token).await {
+        Ok(user) => user.role == "admin" || can_edit_job(pool, &user, job).await,
+        Err(_) => false,
+    }
+}
+
+pub async fn get_job_by_id(
+    pool: Data<PgPool>,
+    analytics: Data<AnalyticsRecorder>,
+    job_id: Path<i32>,
+    token: Option<String>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let mut job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if job.moderation_status != "approved" {
+        if !can_view_unapproved(&pool, token, &job).await {
+            return HttpResponse::NotFound().json("Job not found");
+        }
+    } else {
+        analytics.record_view(job.id, &req);
+    }
+
+    if localize(&pool, vec![&mut job], &requested_locales(&req))
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to get job");
+    }
+
+    HttpResponse::Ok()
+        .insert_header((ETAG, etag(job.version)))
+        .insert_header((CONTENT_LANGUAGE, job.language.clone()))
+        .insert_header((VARY, "Accept-Language"))
+        .json(job)
+}
+
+pub async fn record_apply_click(
+    pool: Data<PgPool>,
+    analytics: Data<AnalyticsRecorder>,
+    job_id: Path<i32>,
+    req: HttpRequest,
+) -> HttpResponse {
     match Job::find_by_id(&pool, &job_id).await {
-        Ok(job) => HttpResponse::Ok().json(job),
-        Err(_) => HttpResponse::NotFound().json("Job not found"),
+        Ok(job) if job.moderation_status == "approved" => {
+            analytics.record_apply_click(job.id, &req);
+            HttpResponse::Accepted().finish()
+        }
+        _ => HttpResponse::NotFound().json("Job not found"),
     }
 }
 
-pub async fn get_jobs(pool: Data<PgPool>) -> HttpResponse {
-    match Job::find_all(&pool).await {
-        Ok(jobs) => HttpResponse::Ok().json(jobs),
-        Err(_) => HttpResponse::InternalServerError().json("Failed to find jobs"),
+pub async fn get_job_stats(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    query: Query<StatsQuery>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to view these stats");
+    }
+
+    let days = query.days.unwrap_or(DEFAULT_STATS_DAYS);
+    if !(1..=MAX_STATS_DAYS).contains(&days) {
+        return HttpResponse::BadRequest()
+            .json(format!("days must be between 1 and {MAX_STATS_DAYS}"));
+    }
+    let to = Utc::now().date_naive();
+    let from = to - Duration::days(days - 1);
+
+    let daily = match JobEvent::daily_stats(&pool, &job.id, from, to).await {
+        Ok(daily) => daily,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to get job stats"),
+    };
+
+    let views: i64 = daily.iter().map(|day| day.views).sum();
+    let apply_clicks: i64 = daily.iter().map(|day| day.apply_clicks).sum();
+    let applications: i64 = daily.iter().map(|day| day.applications).sum();
+    let featured_impressions: i64 = daily.iter().map(|day| day.featured_impressions).sum();
+    let featured_clicks: i64 = daily.iter().map(|day| day.featured_clicks).sum();
+    let conversion_rate = if views == 0 {
+        0.0
+    } else {
+        applications as f64 / views as f64
+    };
+
+    HttpResponse::Ok().json(JobStats {
+        job_id: job.id,
+        views,
+        apply_clicks,
+        applications,
+        conversion_rate,
+        featured_impressions,
+        featured_clicks,
+        daily,
+    })
+}
+
+/// Lists open jobs matching the filters, with jobs that have an active
+/// featured placement moved into the featured slots.
+pub async fn get_jobs(
+    pool: Data<PgPool>,
+    analytics: Data<AnalyticsRecorder>,
+    filter: Query<JobFilter>,
+    token: Option<String>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let search = match job_search(&filter) {
+        Ok(search) => search,
+        Err(response) => return response,
+    };
+
+    let mut jobs = match Job::search(&pool, &search, None, None).await {
+        Ok(jobs) => jobs,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to find jobs"),
+    };
+    if localize(&pool, jobs.iter_mut().collect(), &requested_locales(&req))
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to find jobs");
+    }
+
+    let job_ids: Vec<i32> = jobs.iter().map(|job| job.id).collect();
+    let placements = match FeaturedPlacement::find_active(&pool, &job_ids).await {
+        Ok(placements) => placements,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to find jobs"),
+    };
+    let jobs = mix_featured(jobs, &placements);
+
+    // Anonymous visitors and invalid tokens simply get no saved flags.
+    let user = match &token {
+        Some(token) => get_user_from_token(&pool, token).await.ok(),
+        None => None,
+    };
+    let saved = match user {
+        Some(user) if user.role == "job_seeker" => {
+            match SavedJob::find_saved_job_ids(&pool, &user.id, &job_ids).await {
+                Ok(saved) => saved,
+                Err(_) => return HttpResponse::InternalServerError().json("Failed to find jobs"),
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    let listings: Vec<JobListing> = jobs
+        .into_iter()
+        .map(|(job, featured)| {
+            if featured {
+                analytics.record_featured_impression(job.id, &req);
+            }
+            JobListing {
+                saved: saved.contains(&job.id),
+                featured,
+                job,
+            }
+        })
+        .collect();
+
+    HttpResponse::Ok().json(listings)
+}
+
+/// Open jobs ranked for the seeker, each with the reasons it was picked.
+pub async fn get_recommended_jobs(
+    pool: Data<PgPool>,
+    pagination: Query<Pagination>,
+    token: String,
+    req: HttpRequest,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if user.role != "job_seeker" {
+        return HttpResponse::Forbidden().json("Only job seekers get recommendations");
+    }
+
+    let recommendations = match recommend(&pool, &user.id).await {
+        Ok(recommendations) => recommendations,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to recommend jobs"),
+    };
+
+    let total = recommendations.len() as i64;
+    let mut items: Vec<Recommendation> = recommendations
+        .into_iter()
+        .skip(pagination.offset() as usize)
+        .take(pagination.per_page() as usize)
+        .collect();
+    let jobs = items.iter_mut().map(|item| &mut item.job).collect();
+    if localize(&pool, jobs, &requested_locales(&req))
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to recommend jobs");
+    }
+
+    HttpResponse::Ok().json(Page::new(items, &pagination, total))
+}
+
+/// Other open jobs most like this one, best match first.
+pub async fn get_similar_jobs(
+    pool: Data<PgPool>,
+    cache: Data<SimilarJobsCache>,
+    job_id: Path<i32>,
+    query: Query<SimilarJobsQuery>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let limit = query.limit.unwrap_or(DEFAULT_SIMILAR_LIMIT);
+    if !(1..=MAX_SIMILAR_LIMIT).contains(&limit) {
+        return HttpResponse::BadRequest()
+            .json(format!("limit must be between 1 and {MAX_SIMILAR_LIMIT}"));
+    }
+
+    match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) if job.moderation_status == "approved" => {}
+        _ => return HttpResponse::NotFound().json("Job not found"),
     }
+
+    let scores = match cache
+        .similar_jobs(&pool, *job_id, query.exclude_same_employer, limit)
+        .await
+    {
+        Ok(scores) => scores,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to find similar jobs"),
+    };
+
+    let ids: Vec<i32> = scores.iter().map(|score| score.job_id).collect();
+    let mut jobs = match Job::find_by_ids(&pool, &ids).await {
+        Ok(jobs) => jobs,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to find similar jobs"),
+    };
+    if localize(&pool, jobs.iter_mut().collect(), &requested_locales(&req))
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to find similar jobs");
+    }
+
+    let mut by_id: HashMap<i32, Job> = jobs.into_iter().map(|job| (job.id, job)).collect();
+    let similar: Vec<SimilarJob> = scores
+        .into_iter()
+        .filter_map(|score| {
+            by_id.remove(&score.job_id).map(|job| SimilarJob {
+                job,
+                score: score.score,
+            })
+        })
+        .collect();
+
+    HttpResponse::Ok().json(similar)
 }
 
 pub async fn update_job(
@@ -70,30 +765,178 @@ pub async fn update_job(
     job_id: Path<i32>,
     form: Json<UpdateJobRequest>,
     token: String,
+    req: HttpRequest,
 ) -> HttpResponse {
     let user = match get_user_from_token(&pool, &token).await {
         Ok(user) => user,
         Err(response) => return response,
     };
 
-    let job = Job::find_by_id(&pool, &job_id).await.unwrap();
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
 
-    if user.id != job.employer_id {
+    if !can_edit_job(&pool, &user, &job).await {
         return HttpResponse::Forbidden().json("You do not have permission to update this job");
     }
 
-    match Job::update(
-        &pool,
-        &job_id,
+    if Category::find_by_id(&pool, &form.category_id)
+        .await
+        .is_err()
+    {
+        return HttpResponse::BadRequest().json("Category not found");
+    }
+
+    if let Err(error) = validate_expires_at(form.expires_at) {
+        return HttpResponse::BadRequest().json(error);
+    }
+
+    let mut input = match build_job_input(
         &form.title,
         &form.description,
         &form.location,
+        &form.location_fields,
         form.salary,
-        &form.category,
-    )
-    .await
+        form.category_id,
+        form.expires_at,
+    ) {
+        Ok(input) => input,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+    input.language = match parse_language(form.language.as_deref(), &job.language) {
+        Ok(language) => language,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+    if let Err(response) = check_language_change(&pool, &job, &input.language).await {
+        return response;
+    }
+
+    let duplicates = match check_duplicates(&pool, &job.company_id, Some(job.id), &input).await {
+        Ok(duplicates) => duplicates,
+        Err(response) => return response,
+    };
+
+    let flags = moderation::check(&input.title, &input.description);
+    match Job::update(&pool, &job_id, &input, expected_version, &user.id, &flags).await {
+        Ok(Some(job)) => HttpResponse::Ok()
+            .insert_header((ETAG, etag(job.version)))
+            .json(CheckedJob { job, duplicates }),
+        Ok(None) => precondition_failed(),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to update job"),
+    }
+}
+
+/// Updates only the fields present in the body. Location fields are replaced
+/// as a group, so sending any of them re-geocodes the posting. Without
+/// `If-Match` the patch still fails with 412 if the job changes between being
+/// read and written, rather than overwriting the other edit.
+pub async fn patch_job(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    form: Json<PatchJobRequest>,
+    token: String,
+    req: HttpRequest,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to update this job");
+    }
+
+    if expected_version.is_some_and(|version| version != job.version) {
+        return precondition_failed();
+    }
+
+    if let Some(category_id) = form.category_id
+        && Category::find_by_id(&pool, &category_id).await.is_err()
     {
-        Ok(job) => HttpResponse::Ok().json(job),
+        return HttpResponse::BadRequest().json("Category not found");
+    }
+
+    if let Some(expires_at) = form.expires_at
+        && let Err(error) = validate_expires_at(expires_at)
+    {
+        return HttpResponse::BadRequest().json(error);
+    }
+
+    let fields = &form.location_fields;
+    let location_changed = form.location.is_some()
+        || fields.country.is_some()
+        || fields.region.is_some()
+        || fields.city.is_some()
+        || fields.postal_code.is_some()
+        || fields.latitude.is_some()
+        || fields.longitude.is_some();
+    let location_fields = if location_changed {
+        LocationFields {
+            country: fields.country.clone(),
+            region: fields.region.clone(),
+            city: fields.city.clone(),
+            postal_code: fields.postal_code.clone(),
+            latitude: fields.latitude,
+            longitude: fields.longitude,
+        }
+    } else {
+        LocationFields {
+            country: job.country.clone(),
+            region: job.region.clone(),
+            city: job.city.clone(),
+            postal_code: job.postal_code.clone(),
+            latitude: job.latitude,
+            longitude: job.longitude,
+        }
+    };
+
+    let mut input = match build_job_input(
+        form.title.as_deref().unwrap_or(&job.title),
+        form.description.as_deref().unwrap_or(&job.description),
+        form.location.as_deref().unwrap_or(&job.location),
+        &location_fields,
+        form.salary.unwrap_or(job.salary),
+        form.category_id.unwrap_or(job.category_id),
+        form.expires_at.unwrap_or(job.expires_at),
+    ) {
+        Ok(input) => input,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+    input.language = match parse_language(form.language.as_deref(), &job.language) {
+        Ok(language) => language,
+        Err(error) => return HttpResponse::BadRequest().json(error),
+    };
+    if let Err(response) = check_language_change(&pool, &job, &input.language).await {
+        return response;
+    }
+
+    let duplicates = match check_duplicates(&pool, &job.company_id, Some(job.id), &input).await {
+        Ok(duplicates) => duplicates,
+        Err(response) => return response,
+    };
+
+    let flags = moderation::check(&input.title, &input.description);
+    match Job::update(&pool, &job_id, &input, Some(job.version), &user.id, &flags).await {
+        Ok(Some(job)) => HttpResponse::Ok()
+            .insert_header((ETAG, etag(job.version)))
+            .json(CheckedJob { job, duplicates }),
+        Ok(None) => precondition_failed(),
         Err(_) => HttpResponse::InternalServerError().json("Failed to update job"),
     }
 }
@@ -104,14 +947,240 @@ pub async fn delete_job(pool: Data<PgPool>, job_id: Path<i32>, token: String) ->
         Err(response) => return response,
     };
 
-    let job = Job::find_by_id(&pool, &job_id).await.unwrap();
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
 
-    if user.id != job.employer_id {
+    if !can_delete_job(&pool, &user, &job).await {
         return HttpResponse::Forbidden().json("You do not have permission to delete this job");
     }
 
+    if Notification::notify_job_closed(&pool, &job.id, &job.title)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to delete job");
+    }
+
     match Job::delete(&pool, &job_id).await {
         Ok(_) => HttpResponse::Ok().json("Job deleted"),
         Err(_) => HttpResponse::InternalServerError().json("Failed to delete job"),
     }
 }
+
+/// Clusters of open postings that look like reposts of each other.
+pub async fn get_duplicate_report(pool: Data<PgPool>, token: String) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    match find_clusters(&pool).await {
+        Ok(clusters) => HttpResponse::Ok().json(clusters),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to build duplicate report"),
+    }
+}
+
+pub async fn restore_job(pool: Data<PgPool>, job_id: Path<i32>, token: String) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    match Job::restore(&pool, &job_id).await {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(_) => HttpResponse::NotFound().json("Deleted job not found"),
+    }
+}
+
+pub async fn close_job(pool: Data<PgPool>, job_id: Path<i32>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to close this job");
+    }
+
+    if job.status == "closed" {
+        return HttpResponse::Conflict().json("Job is already closed");
+    }
+    if job.status == "draft" {
+        return HttpResponse::Conflict().json("Drafts cannot be closed; delete them instead");
+    }
+
+    let job = match Job::close(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to close job"),
+    };
+
+    match Notification::notify_job_closed(&pool, &job.id, &job.title).await {
+        Ok(_) => HttpResponse::Ok().json(job),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to notify seekers"),
+    }
+}
+
+/// Copies the posting into a new draft for the same company.
+pub async fn clone_job(pool: Data<PgPool>, job_id: Path<i32>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to clone this job");
+    }
+
+    match Job::clone_as_draft(&pool, &job, &user.id).await {
+        Ok(draft) => HttpResponse::Ok().json(draft),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to clone job"),
+    }
+}
+
+/// Opens a draft, running the same checks as a new posting.
+pub async fn publish_job(pool: Data<PgPool>, job_id: Path<i32>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to publish this job");
+    }
+
+    if job.status != "draft" {
+        return HttpResponse::Conflict().json("Only drafts can be published");
+    }
+
+    if let Err(error) = validate_expires_at(job.expires_at) {
+        return HttpResponse::BadRequest().json(error);
+    }
+
+    let input = JobInput::from(&job);
+    let duplicates = match check_duplicates(&pool, &job.company_id, Some(job.id), &input).await {
+        Ok(duplicates) => duplicates,
+        Err(response) => return response,
+    };
+
+    let flags = moderation::check(&job.title, &job.description);
+    match Job::publish(&pool, &job.id, &flags).await {
+        Ok(Some(job)) => HttpResponse::Ok().json(CheckedJob { job, duplicates }),
+        Ok(None) => HttpResponse::Conflict().json("Only drafts can be published"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to publish job"),
+    }
+}
+
+/// Sets when a draft goes live and, optionally, how long it then stays open.
+pub async fn schedule_job(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    form: Json<ScheduleJobRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to schedule this job");
+    }
+
+    if job.status != "draft" {
+        return HttpResponse::Conflict().json("Only drafts can be scheduled");
+    }
+
+    let publish_at = match form.publish_at {
+        Some(publish_at) => match resolve_publish_at(publish_at, form.timezone.as_deref()) {
+            Ok(publish_at) => Some(publish_at),
+            Err(error) => return HttpResponse::BadRequest().json(error),
+        },
+        None => None,
+    };
+
+    let expires_at = match form.expires_after_days {
+        Some(days) => {
+            if !(1..=MAX_LIFETIME_DAYS).contains(&days) {
+                return HttpResponse::BadRequest().json(format!(
+                    "expires_after_days must be between 1 and {MAX_LIFETIME_DAYS}"
+                ));
+            }
+            let Some(publish_at) = publish_at else {
+                return HttpResponse::BadRequest().json("expires_after_days requires publish_at");
+            };
+            Some(publish_at + Duration::days(days))
+        }
+        None => job.expires_at,
+    };
+    if let (Some(publish_at), Some(expires_at)) = (publish_at, expires_at)
+        && expires_at <= publish_at
+    {
+        return HttpResponse::BadRequest().json("expires_at must be after publish_at");
+    }
+
+    match Job::schedule(&pool, &job.id, publish_at, expires_at, &user.id).await {
+        Ok(Some(job)) => HttpResponse::Ok().json(job),
+        Ok(None) => HttpResponse::Conflict().json("Only drafts can be scheduled"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to schedule job"),
+    }
+}
+
+/// The page the link in the expiry reminder email opens. It only asks for
+/// confirmation: mail scanners and link prefetchers follow every link, so
+/// the extension itself happens on POST.
+pub async fn get_extend_confirmation(
+    pool: Data<PgPool>,
+    extend_token: Path<String>,
+) -> HttpResponse {
+    let job = match Job::find_by_extend_token(&pool, &extend_token).await {
+        Ok(Some(job)) => job,
+        Ok(None) => {
+            return HttpResponse::NotFound().json("This link has expired or was already used");
+        }
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to find job"),
+    };
+
+    let state = match (job.status.as_str(), job.expires_at) {
+        ("open", Some(expires_at)) => {
+            format!("It closes on {} UTC.", expires_at.format("%Y-%m-%d %H:%M"))
+        }
+        ("open", None) => "It is open.".to_string(),
+        _ => "It has closed; extending it reopens it.".to_string(),
+    };
+    let body = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Extend job posting</title></head>\n<body>\n<h1>Extend &quot;{}&quot;</h1>\n<p>{state}</p>\n<form method=\"post\"><button type=\"submit\">Keep it open for another {EXTENSION_DAYS} days</button></form>\n</body></html>\n",
+        escape_xml(&job.title)
+    );
+
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(body)
+}
+
+/// Extends the posting from the confirmation page, so it needs no login.
+pub async fn extend_job(pool: Data<PgPool>, extend_token: Path<String>) -> HttpResponse {
+    match Job::extend(&pool, &extend_token).await {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(_) => HttpResponse::NotFound().json("This link has expired or was already used"),
+    }
+}
@@ -0,0 +1,342 @@
+use crate::models::application::Application;
+use crate::models::job::Job;
+use crate::models::job_question::{ApplicationAnswer, JobQuestion};
+use crate::models::knockout_rule::{
+    KNOCKOUT_REASON, KnockoutRule, KnockoutRuleInput, RuleEvaluation,
+};
+use crate::models::pipeline_stage::{PipelineStage, REJECTED_STAGE};
+use crate::models::user::User;
+use crate::utils::auth::{can_edit_job, get_user_from_token};
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path},
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+
+const NUMERIC_OPERATORS: [&str; 6] = ["eq", "neq", "gt", "gte", "lt", "lte"];
+const MAX_REJECT_DELAY_MINUTES: i32 = 7 * 24 * 60;
+
+#[derive(Deserialize)]
+pub struct KnockoutRuleRequest {
+    pub question_id: i32,
+    pub operator: String,
+    pub value: String,
+    #[serde(default)]
+    pub reject_delay_minutes: i32,
+}
+
+#[derive(Serialize)]
+pub struct RulePreview {
+    pub applicants: usize,
+    pub would_reject: usize,
+}
+
+async fn find_editable_job(pool: &PgPool, job_id: &i32, user: &User) -> Result<Job, HttpResponse> {
+    let job = match Job::find_by_id(pool, job_id).await {
+        Ok(job) => job,
+        Err(_) => return Err(HttpResponse::NotFound().json("Job not found")),
+    };
+
+    if !can_edit_job(pool, user, &job).await {
+        return Err(
+            HttpResponse::Forbidden().json("You do not have permission to manage this job's rules")
+        );
+    }
+
+    Ok(job)
+}
+
+/// Checks the rule against the question it targets. Yes/no and multiple
+/// choice questions only support equality against one of their answers,
+/// numeric questions support any comparison, and free text is not ruled on.
+async fn rule_input(
+    pool: &PgPool,
+    job_id: &i32,
+    rule: &KnockoutRuleRequest,
+) -> Result<(KnockoutRuleInput, JobQuestion), HttpResponse> {
+    let questions = match JobQuestion::find_by_job_id(pool, job_id).await {
+        Ok(questions) => questions,
+        Err(_) => return Err(HttpResponse::InternalServerError().json("Failed to check rule")),
+    };
+    let Some(question) = questions
+        .into_iter()
+        .find(|question| question.id == rule.question_id)
+    else {
+        return Err(HttpResponse::BadRequest().json(format!(
+            "Question {} does not belong to this job",
+            rule.question_id
+        )));
+    };
+
+    let value = rule.value.trim().to_string();
+    let operator = rule.operator.as_str();
+    let valid = match question.kind.as_str() {
+        "yes_no" => (operator == "eq" || operator == "neq") && (value == "yes" || value == "no"),
+        "multiple_choice" => {
+            (operator == "eq" || operator == "neq") && question.options.contains(&value)
+        }
+        "numeric" => NUMERIC_OPERATORS.contains(&operator) && value.parse::<f64>().is_ok(),
+        _ => {
+            return Err(
+                HttpResponse::BadRequest().json("Rules cannot be set on short text questions")
+            );
+        }
+    };
+    if !valid {
+        return Err(HttpResponse::BadRequest().json(format!(
+            "Invalid rule for \"{}\": {} questions take {}",
+            question.prompt,
+            question.kind,
+            match question.kind.as_str() {
+                "yes_no" => "eq or neq with yes or no".to_string(),
+                "multiple_choice" =>
+                    format!("eq or neq with one of: {}", question.options.join(", ")),
+                _ => format!("{} with a number", NUMERIC_OPERATORS.join(", ")),
+            }
+        )));
+    }
+
+    if !(0..=MAX_REJECT_DELAY_MINUTES).contains(&rule.reject_delay_minutes) {
+        return Err(HttpResponse::BadRequest().json(format!(
+            "reject_delay_minutes must be between 0 and {MAX_REJECT_DELAY_MINUTES}"
+        )));
+    }
+
+    let input = KnockoutRuleInput {
+        question_id: question.id,
+        operator: rule.operator.clone(),
+        value,
+        reject_delay_minutes: rule.reject_delay_minutes,
+    };
+    Ok((input, question))
+}
+
+/// Evaluates the job's rules against a new application and records the
+/// results inside the application's transaction. A failed rule rejects the
+/// application, after the shortest delay among the failed rules. Rules on an
+/// optional question the applicant left blank are not evaluated.
+pub async fn apply_knockout_rules(
+    pool: &PgPool,
+    tx: &mut Transaction<'_, Postgres>,
+    application: Application,
+    questions: &[JobQuestion],
+    answers: &[ApplicationAnswer],
+) -> Result<Application, sqlx::Error> {
+    let rules = KnockoutRule::find_by_job_id(pool, &application.job_id).await?;
+    if rules.is_empty() {
+        return Ok(application);
+    }
+
+    let outcomes: Vec<_> = rules
+        .iter()
+        .filter_map(|rule| {
+            let answer = answers
+                .iter()
+                .find(|answer| answer.question_id == Some(rule.question_id))
+                .map(|answer| answer.answer.as_str());
+            let required = questions
+                .iter()
+                .any(|question| question.id == rule.question_id && question.required);
+            if answer.is_none() && !required {
+                return None;
+            }
+            Some(rule.evaluate(answer))
+        })
+        .collect();
+    if outcomes.is_empty() {
+        return Ok(application);
+    }
+    RuleEvaluation::record(tx, &application.id, &outcomes).await?;
+
+    let delay = outcomes
+        .iter()
+        .filter(|outcome| !outcome.passed)
+        .map(|outcome| outcome.reject_delay_minutes)
+        .min();
+    match delay {
+        None => Ok(application),
+        Some(0) => Ok(Application::update_status_in(
+            tx,
+            &application.id,
+            &application.status,
+            REJECTED_STAGE,
+            None,
+            Some(KNOCKOUT_REASON),
+            None,
+        )
+        .await?
+        .unwrap_or(application)),
+        Some(minutes) => {
+            let reject_at = Utc::now().naive_utc() + Duration::minutes(minutes as i64);
+            Application::schedule_auto_reject(tx, &application.id, reject_at).await?;
+            Ok(application)
+        }
+    }
+}
+
+pub async fn get_knockout_rules(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = find_editable_job(&pool, &job_id, &user).await {
+        return response;
+    }
+
+    match KnockoutRule::find_by_job_id(&pool, &job_id).await {
+        Ok(rules) => HttpResponse::Ok().json(rules),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get rules"),
+    }
+}
+
+pub async fn create_knockout_rule(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    form: Json<KnockoutRuleRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = find_editable_job(&pool, &job_id, &user).await {
+        return response;
+    }
+
+    let (input, _) = match rule_input(&pool, &job_id, &form).await {
+        Ok(rule) => rule,
+        Err(response) => return response,
+    };
+
+    match KnockoutRule::create(&pool, &job_id, &input).await {
+        Ok(rule) => HttpResponse::Ok().json(rule),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to create rule"),
+    }
+}
+
+/// Counts how many applicants still at the first pipeline stage the rule
+/// would reject, without saving it or touching any application.
+pub async fn preview_knockout_rule(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    form: Json<KnockoutRuleRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match find_editable_job(&pool, &job_id, &user).await {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+
+    let (input, question) = match rule_input(&pool, &job_id, &form).await {
+        Ok(rule) => rule,
+        Err(response) => return response,
+    };
+
+    let stages = match PipelineStage::find_for_company(&pool, &job.company_id).await {
+        Ok(stages) => stages,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to preview rule"),
+    };
+    let Some(first_stage) = stages.first() else {
+        return HttpResponse::InternalServerError().json("Failed to preview rule");
+    };
+    let answers =
+        match KnockoutRule::pending_answers(&pool, &job_id, &question.id, &first_stage.key).await {
+            Ok(answers) => answers,
+            Err(_) => return HttpResponse::InternalServerError().json("Failed to preview rule"),
+        };
+
+    let rule = KnockoutRule {
+        id: 0,
+        job_id: *job_id,
+        question_id: question.id,
+        prompt: question.prompt,
+        kind: question.kind,
+        operator: input.operator,
+        value: input.value,
+        reject_delay_minutes: input.reject_delay_minutes,
+        created_at: Utc::now().naive_utc(),
+    };
+    let would_reject = answers
+        .iter()
+        .filter(|answer| !rule.passes(answer.as_deref()))
+        .count();
+
+    HttpResponse::Ok().json(RulePreview {
+        applicants: answers.len(),
+        would_reject,
+    })
+}
+
+pub async fn delete_knockout_rule(
+    pool: Data<PgPool>,
+    path: Path<(i32, i32)>,
+    token: String,
+) -> HttpResponse {
+    let (job_id, rule_id) = path.into_inner();
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = find_editable_job(&pool, &job_id, &user).await {
+        return response;
+    }
+
+    match KnockoutRule::find_by_id(&pool, &rule_id).await {
+        Ok(rule) if rule.job_id == job_id => {}
+        _ => return HttpResponse::NotFound().json("Rule not found"),
+    }
+
+    match KnockoutRule::delete(&pool, &rule_id).await {
+        Ok(_) => HttpResponse::Ok().json("Rule deleted"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to delete rule"),
+    }
+}
+
+/// Rule results for an application, visible to the hiring company's members
+/// and admins only. Applicants never see them, so a delayed rejection stays
+/// delayed and does not reveal which answer failed.
+pub async fn get_rule_evaluations(
+    pool: Data<PgPool>,
+    application_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let application = match Application::find_by_id(&pool, &application_id).await {
+        Ok(application) => application,
+        Err(_) => return HttpResponse::NotFound().json("Application not found"),
+    };
+
+    if user.role != "admin" {
+        let job = match Job::find_by_id(&pool, &application.job_id).await {
+            Ok(job) => job,
+            Err(_) => return HttpResponse::NotFound().json("Application not found"),
+        };
+        if !can_edit_job(&pool, &user, &job).await {
+            return HttpResponse::Forbidden()
+                .json("You do not have permission to view this application's rule results");
+        }
+    }
+
+    match RuleEvaluation::find_by_application_id(&pool, &application.id).await {
+        Ok(evaluations) => HttpResponse::Ok().json(evaluations),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get rule evaluations"),
+    }
+}
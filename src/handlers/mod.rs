@@ -1,4 +1,20 @@
 pub mod applications;
 pub mod auth;
+pub mod categories;
+pub mod companies;
+pub mod featured_placements;
+pub mod feeds;
+pub mod job_imports;
+pub mod job_questions;
+pub mod job_revisions;
+pub mod job_templates;
+pub mod job_translations;
 pub mod jobs;
-pub mod users;
\ No newline at end of file
+pub mod knockout_rules;
+pub mod moderation;
+pub mod notifications;
+pub mod pipelines;
+pub mod saved_jobs;
+pub mod saved_searches;
+pub mod skills;
+pub mod users;
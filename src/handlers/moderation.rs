@@ -0,0 +1,222 @@
+use crate::models::company::Company;
+use crate::models::job::Job;
+use crate::models::moderation_event::ModerationEvent;
+use crate::models::notification::Notification;
+use crate::models::user::User;
+use crate::services::feeds::app_url;
+use crate::services::mailer::Mailer;
+use crate::utils::auth::{can_edit_job, get_admin_from_token, get_user_from_token};
+use crate::utils::pagination::{Page, Pagination};
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path, Query},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+const MAX_REASON_LENGTH: usize = 2000;
+
+#[derive(Deserialize)]
+pub struct ModerationRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct VerificationRequest {
+    pub verified: bool,
+}
+
+pub async fn get_moderation_queue(
+    pool: Data<PgPool>,
+    pagination: Query<Pagination>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    let items = match ModerationEvent::find_queue(&pool, pagination.per_page(), pagination.offset())
+        .await
+    {
+        Ok(items) => items,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json("Failed to get moderation queue");
+        }
+    };
+    let total = match ModerationEvent::count_queue(&pool).await {
+        Ok(total) => total,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json("Failed to get moderation queue");
+        }
+    };
+
+    HttpResponse::Ok().json(Page::new(items, &pagination, total))
+}
+
+/// The posting's submissions and moderator decisions, oldest first.
+pub async fn get_job_moderation(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if user.role != "admin" && !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden()
+            .json("You do not have permission to view this job's moderation history");
+    }
+
+    match ModerationEvent::find_by_job_id(&pool, &job.id).await {
+        Ok(events) => HttpResponse::Ok().json(events),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get moderation history"),
+    }
+}
+
+/// Lets the employer who posted the job know the outcome, in the app and by
+/// email. Failing to send the email does not undo the decision.
+async fn notify_employer(
+    pool: &PgPool,
+    mailer: &Mailer,
+    job: &Job,
+    status: &str,
+    reason: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    // The poster's account may have been purged since; nobody to tell then.
+    let Some(employer_id) = job.employer_id else {
+        return Ok(());
+    };
+    let (kind, outcome) = match status {
+        "approved" => ("job_approved", "has been approved and is now live"),
+        "rejected" => ("job_rejected", "has been rejected"),
+        _ => (
+            "job_changes_requested",
+            "needs changes before it can go live",
+        ),
+    };
+    let mut message = format!("Your job \"{}\" {outcome}", job.title);
+    if let Some(reason) = reason {
+        message.push_str(&format!(": {reason}"));
+    }
+
+    Notification::create(pool, &employer_id, kind, Some(job.id), &message).await?;
+
+    let employer = User::find_by_id(pool, &employer_id).await?;
+    let body = format!("{message}\n\n{}/api/jobs/{}\n", app_url(), job.id);
+    if let Err(error) = mailer
+        .send(&employer.email, "Your job posting was reviewed", body)
+        .await
+    {
+        eprintln!(
+            "Failed to email moderation result for job {}: {error}",
+            job.id
+        );
+    }
+
+    Ok(())
+}
+
+async fn moderate_job(
+    pool: Data<PgPool>,
+    mailer: Data<Mailer>,
+    job_id: Path<i32>,
+    form: Json<ModerationRequest>,
+    token: String,
+    status: &str,
+) -> HttpResponse {
+    let admin = match get_admin_from_token(&pool, &token).await {
+        Ok(admin) => admin,
+        Err(response) => return response,
+    };
+
+    let reason = form
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    if status != "approved" && reason.is_none() {
+        return HttpResponse::BadRequest().json("A reason is required");
+    }
+    if reason.is_some_and(|reason| reason.len() > MAX_REASON_LENGTH) {
+        return HttpResponse::BadRequest().json(format!(
+            "reason must be at most {MAX_REASON_LENGTH} characters"
+        ));
+    }
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if job.moderation_status == status {
+        return HttpResponse::Conflict().json(format!("Job is already {status}"));
+    }
+
+    let job = match Job::moderate(&pool, &job.id, status, reason, &admin.id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to moderate job"),
+    };
+
+    // The decision is already committed; a failed notification must not
+    // make it look as if it was not.
+    if let Err(error) = notify_employer(&pool, &mailer, &job, status, reason).await {
+        eprintln!("Failed to notify the employer of job {}: {error}", job.id);
+    }
+
+    HttpResponse::Ok().json(job)
+}
+
+pub async fn approve_job(
+    pool: Data<PgPool>,
+    mailer: Data<Mailer>,
+    job_id: Path<i32>,
+    form: Json<ModerationRequest>,
+    token: String,
+) -> HttpResponse {
+    moderate_job(pool, mailer, job_id, form, token, "approved").await
+}
+
+pub async fn reject_job(
+    pool: Data<PgPool>,
+    mailer: Data<Mailer>,
+    job_id: Path<i32>,
+    form: Json<ModerationRequest>,
+    token: String,
+) -> HttpResponse {
+    moderate_job(pool, mailer, job_id, form, token, "rejected").await
+}
+
+pub async fn request_job_changes(
+    pool: Data<PgPool>,
+    mailer: Data<Mailer>,
+    job_id: Path<i32>,
+    form: Json<ModerationRequest>,
+    token: String,
+) -> HttpResponse {
+    moderate_job(pool, mailer, job_id, form, token, "changes_requested").await
+}
+
+/// Marks a company as verified, so its postings skip review unless the
+/// automatic checks flag them. Postings already waiting are not affected.
+pub async fn set_company_verification(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    form: Json<VerificationRequest>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    match Company::set_verified(&pool, &company_id, form.verified).await {
+        Ok(company) => HttpResponse::Ok().json(company),
+        Err(_) => HttpResponse::NotFound().json("Company not found"),
+    }
+}
@@ -0,0 +1,58 @@
+use crate::models::notification::Notification;
+use crate::utils::auth::get_user_from_token;
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, Query},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct NotificationFilter {
+    #[serde(default)]
+    pub unread: bool,
+}
+
+pub async fn get_notifications(
+    pool: Data<PgPool>,
+    filter: Query<NotificationFilter>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match Notification::find_by_user_id(&pool, &user.id, filter.unread).await {
+        Ok(notifications) => HttpResponse::Ok().json(notifications),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get notifications"),
+    }
+}
+
+pub async fn mark_notification_read(
+    pool: Data<PgPool>,
+    notification_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match Notification::mark_read(&pool, &user.id, &notification_id).await {
+        Ok(notification) => HttpResponse::Ok().json(notification),
+        Err(_) => HttpResponse::NotFound().json("Notification not found"),
+    }
+}
+
+pub async fn mark_all_notifications_read(pool: Data<PgPool>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match Notification::mark_all_read(&pool, &user.id).await {
+        Ok(_) => HttpResponse::Ok().json("Notifications marked as read"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to update notifications"),
+    }
+}
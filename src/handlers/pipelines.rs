@@ -0,0 +1,199 @@
+use crate::handlers::companies::{MEMBER_ROLES, require_member_role};
+use crate::models::pipeline_stage::{PipelineStage, PipelineStageInput, REJECTED_STAGE};
+use crate::utils::auth::get_user_from_token;
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+const MAX_STAGES: usize = 20;
+const MAX_KEY_LENGTH: usize = 40;
+const MAX_NAME_LENGTH: usize = 100;
+
+#[derive(Deserialize)]
+pub struct PipelineStageRequest {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub terminal: bool,
+}
+
+#[derive(Deserialize)]
+pub struct PipelineRequest {
+    /// The stages in order. The first is where new applications start.
+    pub stages: Vec<PipelineStageRequest>,
+}
+
+/// Checks a stage list. Keys are short snake_case identifiers, unique within
+/// the pipeline; the first stage cannot be terminal, and a terminal
+/// `rejected` stage is required for knock-out rules.
+fn pipeline_input(form: &PipelineRequest) -> Result<Vec<PipelineStageInput>, HttpResponse> {
+    if form.stages.len() < 2 || form.stages.len() > MAX_STAGES {
+        return Err(HttpResponse::BadRequest().json(format!(
+            "A pipeline must have between 2 and {MAX_STAGES} stages"
+        )));
+    }
+
+    let mut stages: Vec<PipelineStageInput> = Vec::with_capacity(form.stages.len());
+    for stage in &form.stages {
+        let key = stage.key.trim();
+        let valid_key = key.len() <= MAX_KEY_LENGTH
+            && key.starts_with(|c: char| c.is_ascii_lowercase())
+            && key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid_key {
+            return Err(HttpResponse::BadRequest().json(format!(
+                "Stage key \"{key}\" must start with a letter and use only lowercase letters, digits and underscores, up to {MAX_KEY_LENGTH} characters"
+            )));
+        }
+        if stages.iter().any(|existing| existing.key == key) {
+            return Err(HttpResponse::BadRequest().json(format!("Duplicate stage key \"{key}\"")));
+        }
+        let name = stage.name.trim();
+        if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+            return Err(HttpResponse::BadRequest().json(format!(
+                "Stage names must be between 1 and {MAX_NAME_LENGTH} characters"
+            )));
+        }
+        stages.push(PipelineStageInput {
+            key: key.to_string(),
+            name: name.to_string(),
+            terminal: stage.terminal,
+        });
+    }
+
+    if stages[0].terminal {
+        return Err(HttpResponse::BadRequest().json("The first stage cannot be terminal"));
+    }
+    if !stages
+        .iter()
+        .any(|stage| stage.key == REJECTED_STAGE && stage.terminal)
+    {
+        return Err(HttpResponse::BadRequest().json(format!(
+            "A pipeline must have a terminal \"{REJECTED_STAGE}\" stage"
+        )));
+    }
+
+    Ok(stages)
+}
+
+/// Applications must always sit on a stage of their company's pipeline, so
+/// stages that are still in use cannot be dropped.
+async fn check_stages_in_use(
+    pool: &PgPool,
+    company_id: &i32,
+    keys: &[&str],
+) -> Result<(), HttpResponse> {
+    let statuses = match PipelineStage::statuses_in_use(pool, company_id).await {
+        Ok(statuses) => statuses,
+        Err(_) => {
+            return Err(HttpResponse::InternalServerError().json("Failed to check pipeline stages"));
+        }
+    };
+    let missing: Vec<String> = statuses
+        .into_iter()
+        .filter(|status| !keys.contains(&status.as_str()))
+        .collect();
+    if !missing.is_empty() {
+        return Err(HttpResponse::Conflict().json(format!(
+            "Applications are still at these stages: {}",
+            missing.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn get_company_pipeline(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = require_member_role(&pool, &company_id, &user.id, &MEMBER_ROLES).await {
+        return response;
+    }
+
+    match PipelineStage::find_for_company(&pool, &company_id).await {
+        Ok(stages) => HttpResponse::Ok().json(stages),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get pipeline"),
+    }
+}
+
+pub async fn set_company_pipeline(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    form: Json<PipelineRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) =
+        require_member_role(&pool, &company_id, &user.id, &["owner", "admin"]).await
+    {
+        return response;
+    }
+
+    let stages = match pipeline_input(&form) {
+        Ok(stages) => stages,
+        Err(response) => return response,
+    };
+    let keys: Vec<&str> = stages.iter().map(|stage| stage.key.as_str()).collect();
+    if let Err(response) = check_stages_in_use(&pool, &company_id, &keys).await {
+        return response;
+    }
+
+    if PipelineStage::set_for_company(&pool, &company_id, &stages)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to save pipeline");
+    }
+
+    match PipelineStage::find_for_company(&pool, &company_id).await {
+        Ok(stages) => HttpResponse::Ok().json(stages),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get pipeline"),
+    }
+}
+
+/// Goes back to the default pipeline.
+pub async fn reset_company_pipeline(
+    pool: Data<PgPool>,
+    company_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) =
+        require_member_role(&pool, &company_id, &user.id, &["owner", "admin"]).await
+    {
+        return response;
+    }
+
+    let defaults = match PipelineStage::find_default(&pool).await {
+        Ok(stages) => stages,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to reset pipeline"),
+    };
+    let keys: Vec<&str> = defaults.iter().map(|stage| stage.key.as_str()).collect();
+    if let Err(response) = check_stages_in_use(&pool, &company_id, &keys).await {
+        return response;
+    }
+
+    match PipelineStage::reset_for_company(&pool, &company_id).await {
+        Ok(_) => HttpResponse::Ok().json(defaults),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to reset pipeline"),
+    }
+}
@@ -0,0 +1,70 @@
+use crate::models::job::Job;
+use crate::models::saved_job::SavedJob;
+use crate::utils::auth::get_user_from_token;
+use crate::utils::pagination::{Page, Pagination};
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, Query},
+};
+use sqlx::PgPool;
+
+pub async fn save_job(pool: Data<PgPool>, job_id: Path<i32>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if user.role != "job_seeker" {
+        return HttpResponse::Forbidden().json("Only job seekers can save jobs");
+    }
+
+    match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) if job.moderation_status == "approved" && job.status != "draft" => {}
+        _ => return HttpResponse::NotFound().json("Job not found"),
+    }
+
+    match SavedJob::save(&pool, &user.id, &job_id).await {
+        Ok(_) => HttpResponse::Ok().json("Job saved"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to save job"),
+    }
+}
+
+pub async fn unsave_job(pool: Data<PgPool>, job_id: Path<i32>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match SavedJob::unsave(&pool, &user.id, &job_id).await {
+        Ok(_) => HttpResponse::Ok().json("Job removed from saved jobs"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to remove saved job"),
+    }
+}
+
+pub async fn get_saved_jobs(
+    pool: Data<PgPool>,
+    pagination: Query<Pagination>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let total = match SavedJob::count_by_user_id(&pool, &user.id).await {
+        Ok(total) => total,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to get saved jobs"),
+    };
+
+    match SavedJob::find_jobs_by_user_id(
+        &pool,
+        &user.id,
+        pagination.per_page(),
+        pagination.offset(),
+    )
+    .await
+    {
+        Ok(jobs) => HttpResponse::Ok().json(Page::new(jobs, &pagination, total)),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get saved jobs"),
+    }
+}
@@ -0,0 +1,177 @@
+use crate::handlers::jobs::{JobFilter, job_search};
+use crate::models::saved_search::SavedSearch;
+use crate::models::user::User;
+use crate::utils::auth::get_user_from_token;
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+const FREQUENCIES: [&str; 3] = ["instant", "daily", "weekly"];
+
+#[derive(Deserialize)]
+pub struct SavedSearchRequest {
+    pub name: String,
+    #[serde(flatten)]
+    pub filter: JobFilter,
+    pub frequency: Option<String>,
+    pub active: Option<bool>,
+}
+
+fn validate_frequency(frequency: &Option<String>) -> Result<String, HttpResponse> {
+    let frequency = frequency.as_deref().unwrap_or("daily");
+    if !FREQUENCIES.contains(&frequency) {
+        return Err(HttpResponse::BadRequest().json(format!(
+            "Frequency must be one of: {}",
+            FREQUENCIES.join(", ")
+        )));
+    }
+    Ok(frequency.to_string())
+}
+
+/// Loads the saved search, failing unless it belongs to `user`.
+async fn find_own_saved_search(
+    pool: &PgPool,
+    saved_search_id: &i32,
+    user: &User,
+) -> Result<SavedSearch, HttpResponse> {
+    match SavedSearch::find_by_id(pool, saved_search_id).await {
+        Ok(saved_search) if saved_search.user_id == user.id => Ok(saved_search),
+        _ => Err(HttpResponse::NotFound().json("Saved search not found")),
+    }
+}
+
+pub async fn create_saved_search(
+    pool: Data<PgPool>,
+    form: Json<SavedSearchRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if user.role != "job_seeker" {
+        return HttpResponse::Forbidden().json("Only job seekers can save searches");
+    }
+
+    if form.name.trim().is_empty() {
+        return HttpResponse::BadRequest().json("Saved search name must not be empty");
+    }
+
+    let search = match job_search(&form.filter) {
+        Ok(search) => search,
+        Err(response) => return response,
+    };
+    let frequency = match validate_frequency(&form.frequency) {
+        Ok(frequency) => frequency,
+        Err(response) => return response,
+    };
+
+    match SavedSearch::create(&pool, &user.id, form.name.trim(), &search, &frequency).await {
+        Ok(saved_search) => HttpResponse::Ok().json(saved_search),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to create saved search"),
+    }
+}
+
+pub async fn get_saved_searches(pool: Data<PgPool>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match SavedSearch::find_by_user_id(&pool, &user.id).await {
+        Ok(saved_searches) => HttpResponse::Ok().json(saved_searches),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get saved searches"),
+    }
+}
+
+pub async fn get_saved_search_by_id(
+    pool: Data<PgPool>,
+    saved_search_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match find_own_saved_search(&pool, &saved_search_id, &user).await {
+        Ok(saved_search) => HttpResponse::Ok().json(saved_search),
+        Err(response) => response,
+    }
+}
+
+pub async fn update_saved_search(
+    pool: Data<PgPool>,
+    saved_search_id: Path<i32>,
+    form: Json<SavedSearchRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let saved_search = match find_own_saved_search(&pool, &saved_search_id, &user).await {
+        Ok(saved_search) => saved_search,
+        Err(response) => return response,
+    };
+
+    if form.name.trim().is_empty() {
+        return HttpResponse::BadRequest().json("Saved search name must not be empty");
+    }
+
+    let search = match job_search(&form.filter) {
+        Ok(search) => search,
+        Err(response) => return response,
+    };
+    let frequency = match validate_frequency(&form.frequency) {
+        Ok(frequency) => frequency,
+        Err(response) => return response,
+    };
+
+    match SavedSearch::update(
+        &pool,
+        &saved_search_id,
+        form.name.trim(),
+        &search,
+        &frequency,
+        form.active.unwrap_or(saved_search.active),
+    )
+    .await
+    {
+        Ok(saved_search) => HttpResponse::Ok().json(saved_search),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to update saved search"),
+    }
+}
+
+pub async fn delete_saved_search(
+    pool: Data<PgPool>,
+    saved_search_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = find_own_saved_search(&pool, &saved_search_id, &user).await {
+        return response;
+    }
+
+    match SavedSearch::delete(&pool, &saved_search_id).await {
+        Ok(_) => HttpResponse::Ok().json("Saved search deleted"),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to delete saved search"),
+    }
+}
+
+/// Follows the link included in every alert email, so it needs no login.
+pub async fn unsubscribe_saved_search(pool: Data<PgPool>, token: Path<String>) -> HttpResponse {
+    match SavedSearch::unsubscribe(&pool, &token).await {
+        Ok(_) => HttpResponse::Ok().json("You will no longer receive alerts for this search"),
+        Err(_) => HttpResponse::NotFound().json("Saved search not found"),
+    }
+}
@@ -0,0 +1,241 @@
+use crate::models::job::Job;
+use crate::models::skill::Skill;
+use crate::utils::auth::{can_edit_job, get_admin_from_token, get_user_from_token};
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path, Query},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+pub struct SkillSearch {
+    pub q: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateSkillRequest {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateJobSkillsRequest {
+    #[serde(default)]
+    pub required: Vec<String>,
+    #[serde(default)]
+    pub nice_to_have: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateUserSkillsRequest {
+    pub skills: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct JobMatch {
+    pub job: Job,
+    pub score: f64,
+    pub matched_skills: Vec<String>,
+    pub missing_skills: Vec<String>,
+}
+
+/// Resolves skill names against the vocabulary, failing with the list of
+/// names that matched neither a skill nor an alias.
+async fn resolve_skill_ids(pool: &PgPool, names: &[String]) -> Result<Vec<i32>, HttpResponse> {
+    let resolved = match Skill::resolve(pool, names).await {
+        Ok(resolved) => resolved,
+        Err(_) => return Err(HttpResponse::InternalServerError().json("Failed to resolve skills")),
+    };
+
+    let unknown: Vec<String> = resolved
+        .iter()
+        .filter(|skill| skill.skill_id.is_none())
+        .map(|skill| skill.input.clone())
+        .collect();
+    if !unknown.is_empty() {
+        return Err(
+            HttpResponse::BadRequest().json(format!("Unknown skills: {}", unknown.join(", ")))
+        );
+    }
+
+    Ok(resolved
+        .into_iter()
+        .filter_map(|skill| skill.skill_id)
+        .collect())
+}
+
+pub async fn get_skills(pool: Data<PgPool>, search: Query<SkillSearch>) -> HttpResponse {
+    match Skill::search(&pool, search.q.as_deref().unwrap_or("")).await {
+        Ok(skills) => HttpResponse::Ok().json(skills),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get skills"),
+    }
+}
+
+pub async fn create_skill(
+    pool: Data<PgPool>,
+    form: Json<CreateSkillRequest>,
+    token: String,
+) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    if form.name.trim().is_empty() {
+        return HttpResponse::BadRequest().json("Skill name must not be empty");
+    }
+
+    let skill = match Skill::upsert(&pool, form.name.trim()).await {
+        Ok(skill) => skill,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to create skill"),
+    };
+
+    for alias in &form.aliases {
+        if Skill::add_alias(&pool, &skill.id, alias).await.is_err() {
+            return HttpResponse::InternalServerError().json("Failed to add skill alias");
+        }
+    }
+
+    HttpResponse::Ok().json(skill)
+}
+
+pub async fn get_job_skills(pool: Data<PgPool>, job_id: Path<i32>) -> HttpResponse {
+    if Job::find_by_id(&pool, &job_id).await.is_err() {
+        return HttpResponse::NotFound().json("Job not found");
+    }
+
+    match Skill::find_by_job_id(&pool, &job_id).await {
+        Ok(skills) => HttpResponse::Ok().json(skills),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get job skills"),
+    }
+}
+
+pub async fn update_job_skills(
+    pool: Data<PgPool>,
+    job_id: Path<i32>,
+    form: Json<UpdateJobSkillsRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let job = match Job::find_by_id(&pool, &job_id).await {
+        Ok(job) => job,
+        Err(_) => return HttpResponse::NotFound().json("Job not found"),
+    };
+
+    if !can_edit_job(&pool, &user, &job).await {
+        return HttpResponse::Forbidden().json("You do not have permission to update this job");
+    }
+
+    let required = match resolve_skill_ids(&pool, &form.required).await {
+        Ok(ids) => ids,
+        Err(response) => return response,
+    };
+    let nice_to_have = match resolve_skill_ids(&pool, &form.nice_to_have).await {
+        Ok(ids) => ids,
+        Err(response) => return response,
+    };
+
+    if Skill::set_for_job(&pool, &job_id, &required, &nice_to_have)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to update job skills");
+    }
+
+    match Skill::find_by_job_id(&pool, &job_id).await {
+        Ok(skills) => HttpResponse::Ok().json(skills),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get job skills"),
+    }
+}
+
+pub async fn get_my_skills(pool: Data<PgPool>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    match Skill::find_by_user_id(&pool, &user.id).await {
+        Ok(skills) => HttpResponse::Ok().json(skills),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get skills"),
+    }
+}
+
+pub async fn update_my_skills(
+    pool: Data<PgPool>,
+    form: Json<UpdateUserSkillsRequest>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if user.role != "job_seeker" {
+        return HttpResponse::Forbidden().json("Only job seekers can declare skills");
+    }
+
+    let skill_ids = match resolve_skill_ids(&pool, &form.skills).await {
+        Ok(ids) => ids,
+        Err(response) => return response,
+    };
+
+    if Skill::set_for_user(&pool, &user.id, &skill_ids)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json("Failed to update skills");
+    }
+
+    match Skill::find_by_user_id(&pool, &user.id).await {
+        Ok(skills) => HttpResponse::Ok().json(skills),
+        Err(_) => HttpResponse::InternalServerError().json("Failed to get skills"),
+    }
+}
+
+pub async fn get_job_matches(pool: Data<PgPool>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if user.role != "job_seeker" {
+        return HttpResponse::Forbidden().json("Only job seekers can match against jobs");
+    }
+
+    let overlaps = match Skill::overlap_for_user(&pool, &user.id).await {
+        Ok(overlaps) => overlaps,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to match jobs"),
+    };
+
+    let job_ids: Vec<i32> = overlaps.iter().map(|overlap| overlap.job_id).collect();
+    let mut jobs: HashMap<i32, Job> = match Job::find_by_ids(&pool, &job_ids).await {
+        Ok(jobs) => jobs.into_iter().map(|job| (job.id, job)).collect(),
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to match jobs"),
+    };
+
+    let mut matches: Vec<JobMatch> = overlaps
+        .into_iter()
+        .filter_map(|overlap| {
+            let score = overlap.score();
+            Some(JobMatch {
+                job: jobs.remove(&overlap.job_id)?,
+                score,
+                matched_skills: overlap.matched_skills,
+                missing_skills: overlap.missing_skills,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.job.created_at.cmp(&a.job.created_at))
+    });
+
+    HttpResponse::Ok().json(matches)
+}
@@ -1,12 +1,16 @@
-use crate::models::user::User;
-use crate::models::job::Job;
 use crate::models::application::Application;
+use crate::models::job::Job;
+use crate::models::notification::Notification;
+use crate::models::user::User;
+use crate::services::translations::localize;
+use crate::utils::auth::{get_admin_from_token, get_user_from_token};
+use crate::utils::locale::requested_locales;
 use actix_web::{
-    HttpResponse,
+    HttpRequest, HttpResponse,
     web::{Data, Path},
 };
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{Error, PgPool};
 
 #[derive(Deserialize, Serialize)]
 pub struct UserResponse {
@@ -28,16 +32,99 @@ pub async fn get_user_by_id(pool: Data<PgPool>, user_id: Path<i32>) -> HttpRespo
     }
 }
 
-pub async fn get_jobs_of_user(pool: Data<PgPool>, user_id: Path<i32>) -> HttpResponse {
+pub async fn get_jobs_of_user(
+    pool: Data<PgPool>,
+    user_id: Path<i32>,
+    req: HttpRequest,
+) -> HttpResponse {
     match Job::find_by_user_id(&pool, &user_id).await {
-        Ok(jobs) => HttpResponse::Ok().json(jobs),
+        Ok(jobs) => {
+            let mut jobs: Vec<Job> = jobs
+                .into_iter()
+                .filter(|job| job.moderation_status == "approved")
+                .collect();
+            if localize(&pool, jobs.iter_mut().collect(), &requested_locales(&req))
+                .await
+                .is_err()
+            {
+                return HttpResponse::InternalServerError().json("Failed to get jobs");
+            }
+            HttpResponse::Ok().json(jobs)
+        }
         Err(_) => HttpResponse::NotFound().json("Jobs not found"),
     }
 }
 
-pub async fn get_applications_of_user(pool: Data<PgPool>, user_id: Path<i32>) -> HttpResponse {
-    match Application::find_by_user_id(&pool, &user_id).await {
+/// All of the user's applications for themselves and admins; other callers
+/// only see the ones to jobs of companies they belong to.
+pub async fn get_applications_of_user(
+    pool: Data<PgPool>,
+    user_id: Path<i32>,
+    token: String,
+) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let applications = if user.id == *user_id || user.role == "admin" {
+        Application::find_by_user_id(&pool, &user_id).await
+    } else {
+        Application::find_by_user_id_for_member(&pool, &user_id, &user.id).await
+    };
+    match applications {
         Ok(applications) => HttpResponse::Ok().json(applications),
         Err(_) => HttpResponse::NotFound().json("Applications not found"),
     }
-}   
\ No newline at end of file
+}
+
+/// Users may delete their own account; admins may delete anyone's.
+pub async fn delete_user(pool: Data<PgPool>, user_id: Path<i32>, token: String) -> HttpResponse {
+    let user = match get_user_from_token(&pool, &token).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    if user.id != *user_id && user.role != "admin" {
+        return HttpResponse::Forbidden().json("You do not have permission to delete this user");
+    }
+
+    if User::find_by_id(&pool, &user_id).await.is_err() {
+        return HttpResponse::NotFound().json("User not found");
+    }
+
+    let closed = match User::delete(&pool, *user_id).await {
+        Ok(closed) => closed,
+        Err(_) => return HttpResponse::InternalServerError().json("Failed to delete user"),
+    };
+
+    for job in closed {
+        if Notification::notify_job_closed(&pool, &job.id, &job.title)
+            .await
+            .is_err()
+        {
+            return HttpResponse::InternalServerError().json("Failed to notify seekers");
+        }
+    }
+
+    HttpResponse::Ok().json("User deleted")
+}
+
+pub async fn restore_user(pool: Data<PgPool>, user_id: Path<i32>, token: String) -> HttpResponse {
+    if let Err(response) = get_admin_from_token(&pool, &token).await {
+        return response;
+    }
+
+    match User::restore(&pool, &user_id).await {
+        Ok(user) => HttpResponse::Ok().json(UserResponse {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            role: user.role,
+        }),
+        Err(Error::Database(error)) if error.code().as_deref() == Some("23505") => {
+            HttpResponse::Conflict().json("Another account now uses this email or username")
+        }
+        Err(_) => HttpResponse::NotFound().json("Deleted user not found"),
+    }
+}
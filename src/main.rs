@@ -1,13 +1,17 @@
 use actix_cors::Cors;
-use actix_web::{App, HttpRequest, HttpResponse, HttpServer, Responder, get, web};
+use actix_web::{App, HttpServer, web};
 use dotenv::dotenv;
 use routes::*;
+use services::analytics::AnalyticsRecorder;
+use services::mailer::Mailer;
+use services::similar::SimilarJobsCache;
 use sqlx::PgPool;
-use std::{env, vec};
+use std::env;
 
 mod handlers;
 mod models;
 mod routes;
+mod services;
 mod utils;
 
 #[actix_web::main]
@@ -15,6 +19,13 @@ async fn main() -> std::io::Result<()> {
     dotenv().ok();
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let pool = PgPool::connect(&database_url).await.unwrap();
+    services::skills::seed_skills(&pool)
+        .await
+        .expect("Failed to seed skills");
+    let analytics = AnalyticsRecorder::start(pool.clone());
+    let mailer = Mailer::from_env();
+    let similar_jobs = SimilarJobsCache::default();
+    services::scheduler::start(pool.clone(), mailer.clone());
 
     HttpServer::new(move || {
         App::new()
@@ -23,13 +34,23 @@ async fn main() -> std::io::Result<()> {
                     .allow_any_origin()
                     .allow_any_method()
                     .allow_any_header()
-                    .supports_credentials()
+                    .supports_credentials(),
             )
             .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(analytics.clone()))
+            .app_data(web::Data::new(mailer.clone()))
+            .app_data(web::Data::new(similar_jobs.clone()))
             .configure(auth::config)
             .configure(users::config)
             .configure(jobs::config)
             .configure(applications::config)
+            .configure(categories::config)
+            .configure(companies::config)
+            .configure(skills::config)
+            .configure(notifications::config)
+            .configure(saved_searches::config)
+            .configure(feeds::config)
+            .configure(moderation::config)
     })
     .bind("0.0.0.0:8000")?
     .run()
@@ -1,6 +1,8 @@
+use crate::models::application_status_change::ApplicationStatusChange;
+use crate::models::job_question::ApplicationAnswer;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use sqlx::{Error, FromRow, PgPool, query, query_as};
+use sqlx::{Error, FromRow, PgPool, Postgres, Transaction, query, query_as, query_scalar};
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Application {
@@ -9,31 +11,43 @@ pub struct Application {
     pub user_id: i32,
     pub message: String,
     pub status: String,
+    /// The revision of the posting the applicant saw when applying.
+    pub job_revision_id: Option<i32>,
+    pub version: i32,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
 
 impl Application {
-    pub async fn create(
-        pool: &PgPool,
+    /// Creates the application together with its screening answers, at
+    /// `status`, the first stage of the job's pipeline.
+    /// Inserts the application and its answers inside the caller's
+    /// transaction, so the knock-out rules can be evaluated before it commits.
+    pub async fn create_in(
+        tx: &mut Transaction<'_, Postgres>,
         user_id: &i32,
         job_id: &i32,
         message: &str,
+        answers: &[ApplicationAnswer],
+        status: &str,
     ) -> Result<Self, Error> {
         let application = query_as!(
             Application,
             r#"
-                INSERT INTO applications (job_id, user_id, message)
-                VALUES ($1, $2, $3)
-                RETURNING id, job_id, user_id, message, status, created_at, updated_at
+                INSERT INTO applications (job_id, user_id, message, status, job_revision_id)
+                VALUES ($1, $2, $3, $4, (SELECT id FROM job_revisions WHERE job_id = $1 ORDER BY version DESC LIMIT 1))
+                RETURNING id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
             "#,
             job_id,
             user_id,
             message,
+            status,
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        ApplicationAnswer::insert_batch(tx, &application.id, answers).await?;
+
         Ok(application)
     }
 
@@ -41,7 +55,7 @@ impl Application {
         let application = query_as!(
             Application,
             r#"
-                SELECT id, job_id, user_id, message, status, created_at, updated_at
+                SELECT id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
                 FROM applications
                 WHERE id = $1
             "#,
@@ -57,7 +71,7 @@ impl Application {
         let applications = query_as!(
             Application,
             r#"
-                SELECT id, job_id, user_id, message, status, created_at, updated_at
+                SELECT id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
                 FROM applications
                 WHERE user_id = $1
             "#,
@@ -69,11 +83,108 @@ impl Application {
         Ok(applications)
     }
 
+    /// The user's applications to live jobs of companies `member_id` belongs to.
+    pub async fn find_by_user_id_for_member(
+        pool: &PgPool,
+        user_id: &i32,
+        member_id: &i32,
+    ) -> Result<Vec<Self>, Error> {
+        let applications = query_as!(
+            Application,
+            r#"
+                SELECT a.id, a.job_id, a.user_id, a.message, a.status, a.job_revision_id, a.version, a.created_at, a.updated_at
+                FROM applications a
+                JOIN jobs j ON j.id = a.job_id
+                JOIN company_members m ON m.company_id = j.company_id AND m.user_id = $2
+                WHERE a.user_id = $1 AND j.deleted_at IS NULL
+            "#,
+            user_id,
+            member_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(applications)
+    }
+
+    /// The user's own applications plus those to jobs of any company they
+    /// belong to, under the same rule as `can_view_application`.
+    pub async fn find_visible_to(pool: &PgPool, user_id: &i32) -> Result<Vec<Self>, Error> {
+        let applications = query_as!(
+            Application,
+            r#"
+                SELECT a.id, a.job_id, a.user_id, a.message, a.status, a.job_revision_id, a.version, a.created_at, a.updated_at
+                FROM applications a
+                JOIN jobs j ON j.id = a.job_id
+                WHERE a.user_id = $1
+                   OR (
+                       j.deleted_at IS NULL
+                       AND EXISTS (SELECT 1 FROM company_members m WHERE m.company_id = j.company_id AND m.user_id = $1)
+                   )
+                ORDER BY a.created_at DESC, a.id DESC
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(applications)
+    }
+
+    /// A page of the job's applications, oldest first, optionally only those
+    /// at `status`.
+    pub async fn find_by_job_id(
+        pool: &PgPool,
+        job_id: &i32,
+        status: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, Error> {
+        let applications = query_as!(
+            Application,
+            r#"
+                SELECT id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
+                FROM applications
+                WHERE job_id = $1 AND ($2::text IS NULL OR status = $2)
+                ORDER BY created_at, id
+                LIMIT $3 OFFSET $4
+            "#,
+            job_id,
+            status,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(applications)
+    }
+
+    pub async fn count_by_job_id(
+        pool: &PgPool,
+        job_id: &i32,
+        status: Option<&str>,
+    ) -> Result<i64, Error> {
+        let count = query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM applications
+                WHERE job_id = $1 AND ($2::text IS NULL OR status = $2)
+            "#,
+            job_id,
+            status
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
     pub async fn find_all(pool: &PgPool) -> Result<Vec<Self>, Error> {
         let applications = query_as!(
             Application,
             r#"
-                SELECT id, job_id, user_id, message, status, created_at, updated_at
+                SELECT id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
                 FROM applications
             "#
         )
@@ -83,41 +194,138 @@ impl Application {
         Ok(applications)
     }
 
+    /// Moves the application from `from_status` to `to_status`, cancels any
+    /// pending automatic rejection and records the change. Returns `None` when
+    /// the application has left `from_status` or `expected_version` is stale.
     pub async fn update_status(
         pool: &PgPool,
         application_id: &i32,
-        status: &str,
-    ) -> Result<Self, Error> {
+        from_status: &str,
+        to_status: &str,
+        changed_by: Option<i32>,
+        reason: Option<&str>,
+        expected_version: Option<i32>,
+    ) -> Result<Option<Self>, Error> {
+        let mut tx = pool.begin().await?;
+        let application = Self::update_status_in(
+            &mut tx,
+            application_id,
+            from_status,
+            to_status,
+            changed_by,
+            reason,
+            expected_version,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(application)
+    }
+
+    /// [`Application::update_status`] inside the caller's transaction.
+    pub async fn update_status_in(
+        tx: &mut Transaction<'_, Postgres>,
+        application_id: &i32,
+        from_status: &str,
+        to_status: &str,
+        changed_by: Option<i32>,
+        reason: Option<&str>,
+        expected_version: Option<i32>,
+    ) -> Result<Option<Self>, Error> {
         let application = query_as!(
             Application,
             r#"
                 UPDATE applications
-                SET status = $1
+                SET status = $1,
+                    auto_reject_at = NULL,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $2 AND status = $3 AND ($4::int4 IS NULL OR version = $4)
+                RETURNING id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
+            "#,
+            to_status,
+            application_id,
+            from_status,
+            expected_version,
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if application.is_some() {
+            ApplicationStatusChange::record(
+                tx,
+                application_id,
+                from_status,
+                to_status,
+                changed_by,
+                reason,
+            )
+            .await?;
+        }
+
+        Ok(application)
+    }
+
+    pub async fn schedule_auto_reject(
+        tx: &mut Transaction<'_, Postgres>,
+        application_id: &i32,
+        reject_at: NaiveDateTime,
+    ) -> Result<(), Error> {
+        query!(
+            r#"
+                UPDATE applications
+                SET auto_reject_at = $1
                 WHERE id = $2
-                RETURNING id, job_id, user_id, message, status, created_at, updated_at
             "#,
-            status,
+            reject_at,
             application_id
         )
-        .fetch_one(pool)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(application)
+        Ok(())
     }
 
-    pub async fn update(pool: &PgPool, application_id: &i32, message: &str) -> Result<Self, Error> {
+    /// Applications whose automatic rejection is due. Any status change
+    /// clears `auto_reject_at`, so these are all still at their first stage.
+    pub async fn find_due_auto_rejections(pool: &PgPool) -> Result<Vec<Self>, Error> {
+        let applications = query_as!(
+            Application,
+            r#"
+                SELECT id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
+                FROM applications
+                WHERE auto_reject_at <= (now() AT TIME ZONE 'UTC')
+                ORDER BY auto_reject_at
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(applications)
+    }
+
+    /// Like `Job::update`, returns `None` when `expected_version` is stale.
+    pub async fn update(
+        pool: &PgPool,
+        application_id: &i32,
+        message: &str,
+        expected_version: Option<i32>,
+    ) -> Result<Option<Self>, Error> {
         let application = query_as!(
             Application,
             r#"
                 UPDATE applications
-                SET message = $1
-                WHERE id = $2
-                RETURNING id, job_id, user_id, message, status, created_at, updated_at
+                SET message = $1,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $2 AND ($3::int4 IS NULL OR version = $3)
+                RETURNING id, job_id, user_id, message, status, job_revision_id, version, created_at, updated_at
             "#,
             message,
             application_id,
+            expected_version,
         )
-        .fetch_one(pool)
+        .fetch_optional(pool)
         .await?;
 
         Ok(application)
@@ -0,0 +1,63 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, Postgres, Transaction, query, query_as};
+
+/// One move of an application between statuses. `changed_by` is empty for
+/// automatic changes such as knock-out rejections.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ApplicationStatusChange {
+    pub id: i32,
+    pub application_id: i32,
+    pub from_status: String,
+    pub to_status: String,
+    pub changed_by: Option<i32>,
+    pub reason: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl ApplicationStatusChange {
+    pub async fn record(
+        tx: &mut Transaction<'_, Postgres>,
+        application_id: &i32,
+        from_status: &str,
+        to_status: &str,
+        changed_by: Option<i32>,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO application_status_changes (application_id, from_status, to_status, changed_by, reason)
+                VALUES ($1, $2, $3, $4, $5)
+            "#,
+            application_id,
+            from_status,
+            to_status,
+            changed_by,
+            reason
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_application_id(
+        pool: &PgPool,
+        application_id: &i32,
+    ) -> Result<Vec<Self>, Error> {
+        let changes = query_as!(
+            ApplicationStatusChange,
+            r#"
+                SELECT id, application_id, from_status, to_status, changed_by, reason, created_at
+                FROM application_status_changes
+                WHERE application_id = $1
+                ORDER BY created_at, id
+            "#,
+            application_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(changes)
+    }
+}
@@ -0,0 +1,186 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as, query_scalar};
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Category {
+    pub id: i32,
+    pub parent_id: Option<i32>,
+    pub name: String,
+    pub slug: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategoryNode {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    /// Jobs in this category and all of its descendants.
+    pub job_count: i64,
+    pub children: Vec<CategoryNode>,
+}
+
+struct CategoryCount {
+    id: i32,
+    parent_id: Option<i32>,
+    name: String,
+    slug: String,
+    job_count: i64,
+}
+
+impl Category {
+    pub async fn create(
+        pool: &PgPool,
+        parent_id: Option<i32>,
+        name: &str,
+        slug: &str,
+    ) -> Result<Self, Error> {
+        let category = query_as!(
+            Category,
+            r#"
+                INSERT INTO categories (parent_id, name, slug)
+                VALUES ($1, $2, $3)
+                RETURNING id, parent_id, name, slug, created_at, updated_at
+            "#,
+            parent_id,
+            name,
+            slug
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(category)
+    }
+
+    pub async fn find_by_id(pool: &PgPool, category_id: &i32) -> Result<Self, Error> {
+        let category = query_as!(
+            Category,
+            r#"
+                SELECT id, parent_id, name, slug, created_at, updated_at
+                FROM categories
+                WHERE id = $1
+            "#,
+            category_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(category)
+    }
+
+    /// Returns true when `category_id` is `ancestor_id` itself or sits anywhere
+    /// below it, which would make re-parenting `ancestor_id` onto it a cycle.
+    pub async fn is_descendant_of(
+        pool: &PgPool,
+        category_id: &i32,
+        ancestor_id: &i32,
+    ) -> Result<bool, Error> {
+        let found = query_scalar!(
+            r#"
+                WITH RECURSIVE subtree AS (
+                    SELECT id FROM categories WHERE id = $2
+                    UNION ALL
+                    SELECT c.id FROM categories c JOIN subtree s ON c.parent_id = s.id
+                )
+                SELECT EXISTS (SELECT 1 FROM subtree WHERE id = $1) AS "found!"
+            "#,
+            category_id,
+            ancestor_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(found)
+    }
+
+    pub async fn find_tree(pool: &PgPool) -> Result<Vec<CategoryNode>, Error> {
+        let rows = query_as!(
+            CategoryCount,
+            r#"
+                SELECT c.id, c.parent_id, c.name, c.slug, COUNT(j.id) AS "job_count!"
+                FROM categories c
+                LEFT JOIN jobs j ON j.category_id = c.id AND j.deleted_at IS NULL AND j.moderation_status = 'approved'
+                GROUP BY c.id
+                ORDER BY c.name
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut children: HashMap<Option<i32>, Vec<CategoryCount>> = HashMap::new();
+        for row in rows {
+            children.entry(row.parent_id).or_default().push(row);
+        }
+
+        Ok(build_nodes(&mut children, None))
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        category_id: &i32,
+        parent_id: Option<i32>,
+        name: &str,
+        slug: &str,
+    ) -> Result<Self, Error> {
+        let category = query_as!(
+            Category,
+            r#"
+                UPDATE categories
+                SET parent_id = $1,
+                    name = $2,
+                    slug = $3,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $4
+                RETURNING id, parent_id, name, slug, created_at, updated_at
+            "#,
+            parent_id,
+            name,
+            slug,
+            category_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(category)
+    }
+
+    pub async fn delete(pool: &PgPool, category_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                DELETE FROM categories
+                WHERE id = $1
+            "#,
+            category_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn build_nodes(
+    children: &mut HashMap<Option<i32>, Vec<CategoryCount>>,
+    parent_id: Option<i32>,
+) -> Vec<CategoryNode> {
+    let Some(rows) = children.remove(&parent_id) else {
+        return Vec::new();
+    };
+
+    rows.into_iter()
+        .map(|row| {
+            let nested = build_nodes(children, Some(row.id));
+            let job_count = row.job_count + nested.iter().map(|node| node.job_count).sum::<i64>();
+            CategoryNode {
+                id: row.id,
+                name: row.name,
+                slug: row.slug,
+                job_count,
+                children: nested,
+            }
+        })
+        .collect()
+}
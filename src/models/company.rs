@@ -0,0 +1,264 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as, query_scalar};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Company {
+    pub id: i32,
+    pub name: String,
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+    pub description: String,
+    pub size: Option<String>,
+    pub industry: Option<String>,
+    /// Set by admins; postings from unverified companies are reviewed first.
+    pub verified: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+pub struct CompanyInput {
+    pub name: String,
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+    pub description: String,
+    pub size: Option<String>,
+    pub industry: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CompanyMember {
+    pub company_id: i32,
+    pub user_id: i32,
+    pub username: String,
+    pub email: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl Company {
+    /// Creates the company and makes `owner_id` its first owner.
+    pub async fn create(
+        pool: &PgPool,
+        owner_id: &i32,
+        input: &CompanyInput,
+    ) -> Result<Self, Error> {
+        let mut tx = pool.begin().await?;
+
+        let company = query_as!(
+            Company,
+            r#"
+                INSERT INTO companies (name, logo_url, website, description, size, industry)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, name, logo_url, website, description, size, industry, verified, created_at, updated_at
+            "#,
+            input.name,
+            input.logo_url,
+            input.website,
+            input.description,
+            input.size,
+            input.industry
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        query!(
+            r#"
+                INSERT INTO company_members (company_id, user_id, role)
+                VALUES ($1, $2, 'owner')
+            "#,
+            company.id,
+            owner_id
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(company)
+    }
+
+    pub async fn find_by_id(pool: &PgPool, company_id: &i32) -> Result<Self, Error> {
+        let company = query_as!(
+            Company,
+            r#"
+                SELECT id, name, logo_url, website, description, size, industry, verified, created_at, updated_at
+                FROM companies
+                WHERE id = $1
+            "#,
+            company_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(company)
+    }
+
+    pub async fn find_by_user_id(pool: &PgPool, user_id: &i32) -> Result<Vec<Self>, Error> {
+        let companies = query_as!(
+            Company,
+            r#"
+                SELECT c.id, c.name, c.logo_url, c.website, c.description, c.size, c.industry, c.verified, c.created_at, c.updated_at
+                FROM companies c
+                JOIN company_members m ON m.company_id = c.id
+                WHERE m.user_id = $1
+                ORDER BY c.name
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(companies)
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        company_id: &i32,
+        input: &CompanyInput,
+    ) -> Result<Self, Error> {
+        let company = query_as!(
+            Company,
+            r#"
+                UPDATE companies
+                SET name = $1,
+                    logo_url = $2,
+                    website = $3,
+                    description = $4,
+                    size = $5,
+                    industry = $6,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $7
+                RETURNING id, name, logo_url, website, description, size, industry, verified, created_at, updated_at
+            "#,
+            input.name,
+            input.logo_url,
+            input.website,
+            input.description,
+            input.size,
+            input.industry,
+            company_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(company)
+    }
+
+    pub async fn set_verified(
+        pool: &PgPool,
+        company_id: &i32,
+        verified: bool,
+    ) -> Result<Self, Error> {
+        let company = query_as!(
+            Company,
+            r#"
+                UPDATE companies
+                SET verified = $1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $2
+                RETURNING id, name, logo_url, website, description, size, industry, verified, created_at, updated_at
+            "#,
+            verified,
+            company_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(company)
+    }
+}
+
+impl CompanyMember {
+    pub async fn find_by_company_id(pool: &PgPool, company_id: &i32) -> Result<Vec<Self>, Error> {
+        let members = query_as!(
+            CompanyMember,
+            r#"
+                SELECT m.company_id, m.user_id, u.username, u.email, m.role, m.created_at
+                FROM company_members m
+                JOIN users u ON u.id = m.user_id
+                WHERE m.company_id = $1 AND u.deleted_at IS NULL
+                ORDER BY m.created_at
+            "#,
+            company_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    /// Returns the user's role in the company, or `None` if they are not a member.
+    pub async fn find_role(
+        pool: &PgPool,
+        company_id: &i32,
+        user_id: &i32,
+    ) -> Result<Option<String>, Error> {
+        let role = query_scalar!(
+            r#"
+                SELECT role
+                FROM company_members
+                WHERE company_id = $1 AND user_id = $2
+            "#,
+            company_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(role)
+    }
+
+    /// Adds the user to the company, or changes their role if already a member.
+    pub async fn upsert(
+        pool: &PgPool,
+        company_id: &i32,
+        user_id: &i32,
+        role: &str,
+    ) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO company_members (company_id, user_id, role)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (company_id, user_id) DO UPDATE SET role = EXCLUDED.role
+            "#,
+            company_id,
+            user_id,
+            role
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn count_owners(pool: &PgPool, company_id: &i32) -> Result<i64, Error> {
+        let count = query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM company_members
+                WHERE company_id = $1 AND role = 'owner'
+            "#,
+            company_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn delete(pool: &PgPool, company_id: &i32, user_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                DELETE FROM company_members
+                WHERE company_id = $1 AND user_id = $2
+            "#,
+            company_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as};
+
+/// A paid window during which a job is pinned into featured listing slots.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct FeaturedPlacement {
+    pub id: i32,
+    pub job_id: i32,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub weight: i32,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl FeaturedPlacement {
+    pub async fn create(
+        pool: &PgPool,
+        job_id: &i32,
+        starts_at: NaiveDateTime,
+        ends_at: NaiveDateTime,
+        weight: i32,
+        created_by: &i32,
+    ) -> Result<Self, Error> {
+        let placement = query_as!(
+            FeaturedPlacement,
+            r#"
+                INSERT INTO featured_placements (job_id, starts_at, ends_at, weight, created_by)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id, job_id, starts_at, ends_at, weight, created_by, created_at
+            "#,
+            job_id,
+            starts_at,
+            ends_at,
+            weight,
+            created_by
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(placement)
+    }
+
+    pub async fn find_by_job_id(pool: &PgPool, job_id: &i32) -> Result<Vec<Self>, Error> {
+        let placements = query_as!(
+            FeaturedPlacement,
+            r#"
+                SELECT id, job_id, starts_at, ends_at, weight, created_by, created_at
+                FROM featured_placements
+                WHERE job_id = $1
+                ORDER BY starts_at, id
+            "#,
+            job_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(placements)
+    }
+
+    /// Placements running now for any of `job_ids`, one per job, strongest
+    /// weight first.
+    pub async fn find_active(pool: &PgPool, job_ids: &[i32]) -> Result<Vec<Self>, Error> {
+        let placements = query_as!(
+            FeaturedPlacement,
+            r#"
+                SELECT id, job_id, starts_at, ends_at, weight, created_by, created_at
+                FROM (
+                    SELECT DISTINCT ON (job_id) id, job_id, starts_at, ends_at, weight, created_by, created_at
+                    FROM featured_placements
+                    WHERE job_id = ANY($1)
+                      AND starts_at <= (now() AT TIME ZONE 'UTC')
+                      AND ends_at > (now() AT TIME ZONE 'UTC')
+                    ORDER BY job_id, weight DESC, starts_at, id
+                ) active
+                ORDER BY weight DESC, starts_at, id
+            "#,
+            job_ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(placements)
+    }
+
+    /// Returns whether a placement was deleted.
+    pub async fn delete(pool: &PgPool, job_id: &i32, placement_id: &i32) -> Result<bool, Error> {
+        let result = query!(
+            r#"
+                DELETE FROM featured_placements
+                WHERE id = $1 AND job_id = $2
+            "#,
+            placement_id,
+            job_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,60 +1,642 @@
+use crate::models::job_revision::JobRevision;
+use crate::models::moderation_event::ModerationEvent;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use sqlx::{Error, FromRow, PgPool, query, query_as, types::Decimal};
+use sqlx::{
+    Error, FromRow, PgPool, Postgres, Transaction, query, query_as, query_scalar, types::Decimal,
+};
+
+/// How far the extend link in an expiry reminder pushes the expiry back.
+pub const EXTENSION_DAYS: i32 = 30;
+/// How long the extend link in an expiry reminder keeps working.
+pub const EXTEND_TOKEN_VALID_DAYS: i32 = 14;
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Job {
     pub id: i32,
     pub title: String,
     pub description: String,
+    /// The locale `title` and `description` are written in. Read endpoints
+    /// set it to the translation they served.
+    pub language: String,
     pub salary: Decimal,
     pub location: String,
-    pub category: String,
-    pub employer_id: i32,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub category_id: i32,
+    pub company_id: i32,
+    pub employer_id: Option<i32>,
+    /// `draft`, `open` or `closed`. Drafts are only visible to the company.
+    pub status: String,
+    /// `pending`, `approved`, `rejected` or `changes_requested`. Only approved
+    /// postings are public.
+    pub moderation_status: String,
+    /// The posting this one was cloned from.
+    pub source_job_id: Option<i32>,
+    /// The template this one was created from.
+    pub template_id: Option<i32>,
+    /// When a draft is due to be published by the scheduler.
+    pub publish_at: Option<NaiveDateTime>,
+    pub expires_at: Option<NaiveDateTime>,
+    /// The employer's own id for the posting, used to match bulk re-imports.
+    pub external_ref: Option<String>,
+    pub version: i32,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
 
+pub struct JobInput {
+    pub title: String,
+    pub description: String,
+    pub language: String,
+    pub location: String,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub salary: Decimal,
+    pub category_id: i32,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl From<&Job> for JobInput {
+    fn from(job: &Job) -> Self {
+        JobInput {
+            title: job.title.clone(),
+            description: job.description.clone(),
+            language: job.language.clone(),
+            location: job.location.clone(),
+            country: job.country.clone(),
+            region: job.region.clone(),
+            city: job.city.clone(),
+            postal_code: job.postal_code.clone(),
+            latitude: job.latitude,
+            longitude: job.longitude,
+            salary: job.salary,
+            category_id: job.category_id,
+            expires_at: job.expires_at,
+        }
+    }
+}
+
+/// An employer to warn that their posting is about to close.
+pub struct ExpiryReminder {
+    pub job_id: i32,
+    pub title: String,
+    pub employer_id: i32,
+    pub expires_at: NaiveDateTime,
+    pub extend_token: String,
+}
+
+/// How much each signal adds to a similar job's score. Each signal is scaled
+/// to between 0 and 1 before weighting.
+pub struct SimilarityWeights {
+    pub category: f64,
+    pub location: f64,
+    pub salary: f64,
+    pub title: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimilarJobScore {
+    pub job_id: i32,
+    pub score: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct JobSearch {
+    pub q: Option<String>,
+    pub category_id: Option<i32>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub min_salary: Option<Decimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub radius_km: Option<f64>,
+}
+
 impl Job {
     pub async fn create(
         pool: &PgPool,
         user_id: &i32,
-        title: &str,
-        description: &str,
-        location: &str,
-        salary: Decimal,
-        category: &str,
+        company_id: &i32,
+        input: &JobInput,
+        flags: &[String],
+    ) -> Result<Self, Error> {
+        let mut tx = pool.begin().await?;
+        let job = Self::create_in(&mut tx, user_id, company_id, input, None, flags).await?;
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    /// Inserts the job and its first revision inside the caller's transaction.
+    /// Postings from verified companies go live unless the automatic checks
+    /// raised `flags`; everything else waits in the moderation queue.
+    pub async fn create_in(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: &i32,
+        company_id: &i32,
+        input: &JobInput,
+        external_ref: Option<&str>,
+        flags: &[String],
     ) -> Result<Self, Error> {
         let job = query_as!(
             Job,
             r#"
-                INSERT INTO jobs (title, description, location, salary, category, employer_id)
-                VALUES ($1, $2, $3, $4, $5, $6)
-                RETURNING id, title, description, location, salary, category, employer_id, created_at, updated_at
+                INSERT INTO jobs (title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, expires_at, company_id, employer_id, external_ref, moderation_status, published_at, language)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
+                        CASE WHEN cardinality($16::text[]) = 0 AND (SELECT verified FROM companies WHERE id = $13)
+                             THEN 'approved' ELSE 'pending' END,
+                        CASE WHEN cardinality($16::text[]) = 0 AND (SELECT verified FROM companies WHERE id = $13)
+                             THEN (now() AT TIME ZONE 'UTC') END,
+                        $17)
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
             "#,
-            title,
-            description,
-            location,
-            salary,
-            category,
-            user_id
+            input.title,
+            input.description,
+            input.location,
+            input.country,
+            input.region,
+            input.city,
+            input.postal_code,
+            input.latitude,
+            input.longitude,
+            input.salary,
+            input.category_id,
+            input.expires_at,
+            company_id,
+            user_id,
+            external_ref,
+            flags,
+            input.language
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        JobRevision::record(tx, &job.id, Some(user_id)).await?;
+        if job.moderation_status == "pending" {
+            ModerationEvent::record(tx, &job.id, "submitted", None, flags, None).await?;
+        }
+
         Ok(job)
     }
 
-    pub async fn find_by_id(pool: &PgPool, job_id: &i32) -> Result<Self, Error> {
+    pub async fn create_draft(
+        pool: &PgPool,
+        user_id: &i32,
+        company_id: &i32,
+        input: &JobInput,
+        source_job_id: Option<i32>,
+        template_id: Option<i32>,
+        publish_at: Option<NaiveDateTime>,
+    ) -> Result<Self, Error> {
+        let mut tx = pool.begin().await?;
+        let job = Self::create_draft_in(
+            &mut tx,
+            user_id,
+            company_id,
+            input,
+            source_job_id,
+            template_id,
+            publish_at,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    /// Inserts an unpublished posting, optionally recording the job or
+    /// template it was copied from. Drafts skip moderation until
+    /// [`Job::publish`], which the scheduler runs at `publish_at` if one is set.
+    pub async fn create_draft_in(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: &i32,
+        company_id: &i32,
+        input: &JobInput,
+        source_job_id: Option<i32>,
+        template_id: Option<i32>,
+        publish_at: Option<NaiveDateTime>,
+    ) -> Result<Self, Error> {
         let job = query_as!(
             Job,
             r#"
-                SELECT id, title, description, location, salary, category, employer_id, created_at, updated_at
+                INSERT INTO jobs (title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, language, source_job_id, template_id, publish_at, expires_at, status)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, 'draft')
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            input.title,
+            input.description,
+            input.location,
+            input.country,
+            input.region,
+            input.city,
+            input.postal_code,
+            input.latitude,
+            input.longitude,
+            input.salary,
+            input.category_id,
+            company_id,
+            user_id,
+            input.language,
+            source_job_id,
+            template_id,
+            publish_at,
+            input.expires_at
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        JobRevision::record(tx, &job.id, Some(user_id)).await?;
+
+        Ok(job)
+    }
+
+    /// Clones a posting into a draft along with its skills, screening
+    /// questions and translations. Knockout rules refer to the source's
+    /// questions and are not copied.
+    pub async fn clone_as_draft(pool: &PgPool, source: &Job, user_id: &i32) -> Result<Self, Error> {
+        let input = JobInput {
+            expires_at: None,
+            ..JobInput::from(source)
+        };
+
+        let mut tx = pool.begin().await?;
+        let job = Self::create_draft_in(
+            &mut tx,
+            user_id,
+            &source.company_id,
+            &input,
+            Some(source.id),
+            None,
+            None,
+        )
+        .await?;
+
+        query!(
+            r#"
+                INSERT INTO job_skills (job_id, skill_id, required)
+                SELECT $1, skill_id, required FROM job_skills WHERE job_id = $2
+            "#,
+            job.id,
+            source.id
+        )
+        .execute(&mut tx)
+        .await?;
+
+        query!(
+            r#"
+                INSERT INTO job_questions (job_id, position, prompt, kind, required, options, min_value, max_value, max_length)
+                SELECT $1, position, prompt, kind, required, options, min_value, max_value, max_length
+                FROM job_questions WHERE job_id = $2
+            "#,
+            job.id,
+            source.id
+        )
+        .execute(&mut tx)
+        .await?;
+
+        query!(
+            r#"
+                INSERT INTO job_translations (job_id, locale, title, description)
+                SELECT $1, locale, title, description FROM job_translations WHERE job_id = $2
+            "#,
+            job.id,
+            source.id
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    /// Opens a draft, dated from publication so it lists and alerts as new. It
+    /// goes live straight away under the same rules as a new posting and
+    /// waits in the moderation queue otherwise. Returns `None` if the job is
+    /// not a draft.
+    pub async fn publish(
+        pool: &PgPool,
+        job_id: &i32,
+        flags: &[String],
+    ) -> Result<Option<Self>, Error> {
+        let mut tx = pool.begin().await?;
+        let job = Self::publish_in(&mut tx, job_id, flags).await?;
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    /// [`Job::publish`] inside the caller's transaction.
+    pub async fn publish_in(
+        tx: &mut Transaction<'_, Postgres>,
+        job_id: &i32,
+        flags: &[String],
+    ) -> Result<Option<Self>, Error> {
+        let job = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET status = 'open',
+                    moderation_status = CASE
+                        WHEN cardinality($2::text[]) = 0
+                             AND (SELECT verified FROM companies WHERE id = jobs.company_id)
+                        THEN 'approved' ELSE 'pending' END,
+                    published_at = CASE
+                        WHEN cardinality($2::text[]) = 0
+                             AND (SELECT verified FROM companies WHERE id = jobs.company_id)
+                        THEN (now() AT TIME ZONE 'UTC') END,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND status = 'draft' AND deleted_at IS NULL
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            job_id,
+            flags
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(job) = &job
+            && job.moderation_status == "pending"
+        {
+            ModerationEvent::record(tx, job_id, "submitted", None, flags, None).await?;
+        }
+
+        Ok(job)
+    }
+
+    /// Sets or clears when a draft goes live, along with its expiry, and
+    /// records a revision. Returns `None` if the job is not a draft.
+    pub async fn schedule(
+        pool: &PgPool,
+        job_id: &i32,
+        publish_at: Option<NaiveDateTime>,
+        expires_at: Option<NaiveDateTime>,
+        author_id: &i32,
+    ) -> Result<Option<Self>, Error> {
+        let mut tx = pool.begin().await?;
+
+        let job = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET publish_at = $2,
+                    expires_at = $3,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND status = 'draft' AND deleted_at IS NULL
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            job_id,
+            publish_at,
+            expires_at
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        if job.is_some() {
+            JobRevision::record(&mut tx, job_id, Some(author_id)).await?;
+        }
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    /// Locks up to `limit` drafts whose publication time has passed. Rows
+    /// another instance already holds are skipped, so concurrent schedulers
+    /// never publish the same draft twice.
+    pub async fn claim_due_drafts(
+        tx: &mut Transaction<'_, Postgres>,
+        limit: i64,
+    ) -> Result<Vec<Self>, Error> {
+        let jobs = query_as!(
+            Job,
+            r#"
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
                 FROM jobs
+                WHERE status = 'draft' AND publish_at <= (now() AT TIME ZONE 'UTC') AND deleted_at IS NULL
+                ORDER BY publish_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            "#,
+            limit
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    /// Closes up to `limit` open jobs whose expiry has passed, skipping rows
+    /// locked by another instance, and returns them.
+    pub async fn close_expired(pool: &PgPool, limit: i64) -> Result<Vec<Self>, Error> {
+        let jobs = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET status = 'closed',
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id IN (
+                    SELECT id FROM jobs
+                    WHERE status = 'open' AND expires_at <= (now() AT TIME ZONE 'UTC') AND deleted_at IS NULL
+                    ORDER BY expires_at
+                    LIMIT $1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    /// Claims open jobs expiring within `within_hours` whose employer has not
+    /// been reminded yet, handing each a fresh extend token. Claiming with an
+    /// UPDATE keeps concurrent runs from reminding twice.
+    pub async fn claim_expiry_reminders(
+        pool: &PgPool,
+        within_hours: i32,
+    ) -> Result<Vec<ExpiryReminder>, Error> {
+        let reminders = query_as!(
+            ExpiryReminder,
+            r#"
+                UPDATE jobs
+                SET expiry_reminded_at = (now() AT TIME ZONE 'UTC'),
+                    extend_token = gen_random_uuid()::text,
+                    extend_token_expires_at = (now() AT TIME ZONE 'UTC') + make_interval(days => $2)
+                WHERE id IN (
+                    SELECT id FROM jobs
+                    WHERE status = 'open'
+                      AND expiry_reminded_at IS NULL
+                      AND employer_id IS NOT NULL
+                      AND deleted_at IS NULL
+                      AND expires_at > (now() AT TIME ZONE 'UTC')
+                      AND expires_at <= (now() AT TIME ZONE 'UTC') + make_interval(hours => $1)
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING id AS job_id, title, employer_id AS "employer_id!", expires_at AS "expires_at!", extend_token AS "extend_token!"
+            "#,
+            within_hours,
+            EXTEND_TOKEN_VALID_DAYS
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(reminders)
+    }
+
+    /// Gives back a claimed reminder whose email could not be sent, so the
+    /// next run tries again with a fresh token.
+    pub async fn release_expiry_reminder(pool: &PgPool, job_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                UPDATE jobs
+                SET expiry_reminded_at = NULL,
+                    extend_token = NULL,
+                    extend_token_expires_at = NULL
                 WHERE id = $1
             "#,
             job_id
         )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The job an unused, unexpired extend token belongs to.
+    pub async fn find_by_extend_token(
+        pool: &PgPool,
+        extend_token: &str,
+    ) -> Result<Option<Self>, Error> {
+        let job = query_as!(
+            Job,
+            r#"
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+                FROM jobs
+                WHERE extend_token = $1
+                  AND extend_token_expires_at > (now() AT TIME ZONE 'UTC')
+                  AND status <> 'draft'
+                  AND deleted_at IS NULL
+            "#,
+            extend_token
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    /// Pushes the expiry of the job holding `extend_token` back by
+    /// [`EXTENSION_DAYS`], counted from now if it has already passed, and
+    /// reopens it if the scheduler closed it meanwhile. The token works once
+    /// and only until [`EXTEND_TOKEN_VALID_DAYS`] after it was issued.
+    pub async fn extend(pool: &PgPool, extend_token: &str) -> Result<Self, Error> {
+        let mut tx = pool.begin().await?;
+
+        let job = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET expires_at = GREATEST(expires_at, (now() AT TIME ZONE 'UTC')) + make_interval(days => $2),
+                    status = 'open',
+                    expiry_reminded_at = NULL,
+                    extend_token = NULL,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE extend_token = $1
+                  AND extend_token_expires_at > (now() AT TIME ZONE 'UTC')
+                  AND status <> 'draft'
+                  AND deleted_at IS NULL
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            extend_token,
+            EXTENSION_DAYS
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        JobRevision::record(&mut tx, &job.id, job.employer_id.as_ref()).await?;
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    /// Looks up a company's job by its external reference, including
+    /// soft-deleted ones so callers can tell a deleted match from no match.
+    pub async fn find_by_external_ref(
+        tx: &mut Transaction<'_, Postgres>,
+        company_id: &i32,
+        external_ref: &str,
+    ) -> Result<Option<(Self, bool)>, Error> {
+        let row = query!(
+            r#"
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at, deleted_at IS NOT NULL AS "deleted!"
+                FROM jobs
+                WHERE company_id = $1 AND external_ref = $2
+                FOR UPDATE
+            "#,
+            company_id,
+            external_ref
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(row.map(|row| {
+            let job = Job {
+                id: row.id,
+                title: row.title,
+                description: row.description,
+                salary: row.salary,
+                location: row.location,
+                country: row.country,
+                region: row.region,
+                city: row.city,
+                postal_code: row.postal_code,
+                latitude: row.latitude,
+                longitude: row.longitude,
+                category_id: row.category_id,
+                company_id: row.company_id,
+                employer_id: row.employer_id,
+                status: row.status,
+                moderation_status: row.moderation_status,
+                language: row.language,
+                source_job_id: row.source_job_id,
+                template_id: row.template_id,
+                publish_at: row.publish_at,
+                expires_at: row.expires_at,
+                external_ref: row.external_ref,
+                version: row.version,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            };
+            (job, row.deleted)
+        }))
+    }
+
+    pub async fn find_by_id(pool: &PgPool, job_id: &i32) -> Result<Self, Error> {
+        let job = query_as!(
+            Job,
+            r#"
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+                FROM jobs
+                WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            job_id
+        )
         .fetch_one(pool)
         .await?;
 
@@ -65,9 +647,9 @@ impl Job {
         let jobs = query_as!(
             Job,
             r#"
-                SELECT id, title, description, location, salary, category, employer_id, created_at, updated_at
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
                 FROM jobs
-                WHERE employer_id = $1
+                WHERE employer_id = $1 AND deleted_at IS NULL
             "#,
             user_id
         )
@@ -77,13 +659,31 @@ impl Job {
         Ok(jobs)
     }
 
-    pub async fn find_all(pool: &PgPool) -> Result<Vec<Self>, Error> {
+    pub async fn find_by_company_id(pool: &PgPool, company_id: &i32) -> Result<Vec<Self>, Error> {
+        let jobs = query_as!(
+            Job,
+            r#"
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+                FROM jobs
+                WHERE company_id = $1 AND deleted_at IS NULL
+            "#,
+            company_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    pub async fn find_by_ids(pool: &PgPool, job_ids: &[i32]) -> Result<Vec<Self>, Error> {
         let jobs = query_as!(
             Job,
             r#"
-                SELECT id, title, description, location, salary, category, employer_id, created_at, updated_at
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
                 FROM jobs
+                WHERE id = ANY($1) AND deleted_at IS NULL AND moderation_status = 'approved'
             "#,
+            job_ids
         )
         .fetch_all(pool)
         .await?;
@@ -91,15 +691,181 @@ impl Job {
         Ok(jobs)
     }
 
+    /// Searches open jobs. Every filter is optional; a category matches its
+    /// subcategories too. With a point and radius, results are sorted nearest
+    /// first: the `earth_box` condition is a cheap prefilter served by the GiST
+    /// index on `ll_to_earth(latitude, longitude)` and `earth_distance` then
+    /// trims the corners of the box. Otherwise the newest jobs come first.
+    /// The text query is matched against the posting and every translation,
+    /// each with the text search configuration of its own language.
+    pub async fn search(
+        pool: &PgPool,
+        search: &JobSearch,
+        published_after: Option<NaiveDateTime>,
+        published_before: Option<NaiveDateTime>,
+    ) -> Result<Vec<Self>, Error> {
+        let jobs = query_as!(
+            Job,
+            r#"
+                SELECT id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+                FROM jobs
+                WHERE status = 'open'
+                  AND moderation_status = 'approved'
+                  AND deleted_at IS NULL
+                  AND ($1::text IS NULL
+                       OR search_vector @@ websearch_to_tsquery(locale_ts_config(language), $1)
+                       OR EXISTS (
+                           SELECT 1 FROM job_translations t
+                           WHERE t.job_id = jobs.id
+                             AND t.search_vector @@ websearch_to_tsquery(locale_ts_config(t.locale), $1)
+                       ))
+                  AND ($2::int4 IS NULL OR category_id IN (
+                      WITH RECURSIVE subtree AS (
+                          SELECT id FROM categories WHERE id = $2
+                          UNION ALL
+                          SELECT c.id FROM categories c JOIN subtree s ON c.parent_id = s.id
+                      )
+                      SELECT id FROM subtree
+                  ))
+                  AND ($3::text IS NULL OR city ILIKE $3)
+                  AND ($4::text IS NULL OR country ILIKE $4)
+                  AND ($5::numeric IS NULL OR salary >= $5)
+                  AND ($6::float8 IS NULL OR (
+                      earth_box(ll_to_earth($6, $7), $8) @> ll_to_earth(latitude, longitude)
+                      AND earth_distance(ll_to_earth($6, $7), ll_to_earth(latitude, longitude)) <= $8
+                  ))
+                  AND ($9::timestamp IS NULL OR published_at > $9)
+                  AND ($10::timestamp IS NULL OR published_at <= $10)
+                ORDER BY
+                    CASE WHEN $6::float8 IS NULL THEN NULL
+                         ELSE earth_distance(ll_to_earth($6, $7), ll_to_earth(latitude, longitude))
+                    END,
+                    created_at DESC
+            "#,
+            search.q,
+            search.category_id,
+            search.city,
+            search.country,
+            search.min_salary,
+            search.latitude,
+            search.longitude,
+            search.radius_km.map(|radius_km| radius_km * 1000.0),
+            published_after,
+            published_before
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    /// Scores other open jobs against `job_id` and returns the best `limit`.
+    /// Location counts fully for the same spot and fades out at `radius_km`,
+    /// or matches by city when either job has no coordinates; salary counts
+    /// by the ratio of the lower to the higher; titles by trigram similarity.
+    /// Candidates must share the category, a similar title or the area, which
+    /// keeps the scan on indexes.
+    pub async fn find_similar(
+        pool: &PgPool,
+        job_id: &i32,
+        weights: &SimilarityWeights,
+        radius_km: f64,
+        exclude_company: bool,
+        limit: i64,
+    ) -> Result<Vec<SimilarJobScore>, Error> {
+        let scores = query_as!(
+            SimilarJobScore,
+            r#"
+                SELECT id AS "job_id!", score AS "score!"
+                FROM (
+                    SELECT j.id, j.created_at,
+                        CASE WHEN j.category_id = s.category_id THEN $2::float8 ELSE 0 END
+                        + $3 * CASE
+                            WHEN j.latitude IS NOT NULL AND s.latitude IS NOT NULL
+                            THEN GREATEST(0, 1 - earth_distance(
+                                ll_to_earth(s.latitude, s.longitude),
+                                ll_to_earth(j.latitude, j.longitude)
+                            ) / $6)
+                            WHEN lower(j.city) = lower(s.city) THEN 1
+                            ELSE 0
+                          END
+                        + $4 * CASE
+                            WHEN j.salary > 0 AND s.salary > 0
+                            THEN (LEAST(j.salary, s.salary) / GREATEST(j.salary, s.salary))::float8
+                            ELSE 0
+                          END
+                        + $5::float8 * similarity(j.title, s.title) AS score
+                    FROM jobs j
+                    JOIN jobs s ON s.id = $1
+                    WHERE j.id <> s.id
+                      AND j.status = 'open'
+                      AND j.moderation_status = 'approved'
+                      AND j.deleted_at IS NULL
+                      AND (j.expires_at IS NULL OR j.expires_at > (now() AT TIME ZONE 'UTC'))
+                      AND (NOT $7 OR j.company_id <> s.company_id)
+                      AND (j.category_id = s.category_id
+                           OR j.title % s.title
+                           OR (s.latitude IS NOT NULL
+                               AND earth_box(ll_to_earth(s.latitude, s.longitude), $6) @> ll_to_earth(j.latitude, j.longitude)))
+                ) scored
+                ORDER BY score DESC, created_at DESC, id
+                LIMIT $8
+            "#,
+            job_id,
+            weights.category,
+            weights.location,
+            weights.salary,
+            weights.title,
+            radius_km * 1000.0,
+            exclude_company,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(scores)
+    }
+
+    /// A counter that moves whenever a change to jobs commits, on any
+    /// instance. Uncommitted changes are never reflected in it.
+    pub async fn change_generation(pool: &PgPool) -> Result<i64, Error> {
+        let generation = query_scalar!("SELECT generation FROM job_changes")
+            .fetch_one(pool)
+            .await?;
+
+        Ok(generation)
+    }
+
+    /// Applies `input`, bumps the version and records the result as a new
+    /// revision by `author_id`. With `expected_version` set, the update only
+    /// happens if the row is still at that version, and `None` is returned
+    /// otherwise. An approved posting stays live if its company is verified
+    /// and the edit raised no `flags`; any other edit goes back to review.
     pub async fn update(
         pool: &PgPool,
         job_id: &i32,
-        title: &str,
-        description: &str,
-        location: &str,
-        salary: Decimal,
-        category: &str,
-    ) -> Result<Self, Error> {
+        input: &JobInput,
+        expected_version: Option<i32>,
+        author_id: &i32,
+        flags: &[String],
+    ) -> Result<Option<Self>, Error> {
+        let mut tx = pool.begin().await?;
+        let job =
+            Self::update_in(&mut tx, job_id, input, expected_version, author_id, flags).await?;
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    /// [`Job::update`] inside the caller's transaction.
+    pub async fn update_in(
+        tx: &mut Transaction<'_, Postgres>,
+        job_id: &i32,
+        input: &JobInput,
+        expected_version: Option<i32>,
+        author_id: &i32,
+        flags: &[String],
+    ) -> Result<Option<Self>, Error> {
         let job = query_as!(
             Job,
             r#"
@@ -107,17 +873,136 @@ impl Job {
                 SET title = $1,
                     description = $2,
                     location = $3,
-                    salary = $4,
-                    category = $5,
+                    country = $4,
+                    region = $5,
+                    city = $6,
+                    postal_code = $7,
+                    latitude = $8,
+                    longitude = $9,
+                    salary = $10,
+                    category_id = $11,
+                    expires_at = $12,
+                    expiry_reminded_at = CASE WHEN expires_at IS DISTINCT FROM $12 THEN NULL ELSE expiry_reminded_at END,
+                    extend_token = CASE WHEN expires_at IS DISTINCT FROM $12 THEN NULL ELSE extend_token END,
+                    language = $16,
+                    moderation_status = CASE
+                        WHEN moderation_status = 'approved'
+                             AND cardinality($15::text[]) = 0
+                             AND (SELECT verified FROM companies WHERE id = jobs.company_id)
+                        THEN 'approved' ELSE 'pending' END,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $13 AND deleted_at IS NULL AND ($14::int4 IS NULL OR version = $14)
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            input.title,
+            input.description,
+            input.location,
+            input.country,
+            input.region,
+            input.city,
+            input.postal_code,
+            input.latitude,
+            input.longitude,
+            input.salary,
+            input.category_id,
+            input.expires_at,
+            job_id,
+            expected_version,
+            flags,
+            input.language
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(job) = &job {
+            JobRevision::record(tx, job_id, Some(author_id)).await?;
+            if job.moderation_status == "pending" && job.status != "draft" {
+                ModerationEvent::record(tx, job_id, "submitted", None, flags, None).await?;
+            }
+        }
+
+        Ok(job)
+    }
+
+    /// Sends an edit made outside `update_in`, such as a translation, through
+    /// the same review rules inside the caller's transaction.
+    pub async fn review_edit(
+        tx: &mut Transaction<'_, Postgres>,
+        job_id: &i32,
+        flags: &[String],
+    ) -> Result<(), Error> {
+        let job = query!(
+            r#"
+                UPDATE jobs
+                SET moderation_status = CASE
+                        WHEN moderation_status = 'approved'
+                             AND cardinality($2::text[]) = 0
+                             AND (SELECT verified FROM companies WHERE id = jobs.company_id)
+                        THEN 'approved' ELSE 'pending' END,
                     updated_at = CURRENT_TIMESTAMP
-                WHERE id = $6
-                RETURNING id, title, description, location, salary, category, employer_id, created_at, updated_at
-            "#,
-            title,
-            description,
-            location,
-            salary,
-            category,
+                WHERE id = $1
+                RETURNING status, moderation_status
+            "#,
+            job_id,
+            flags
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if job.moderation_status == "pending" && job.status != "draft" {
+            ModerationEvent::record(tx, job_id, "submitted", None, flags, None).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Records a moderator's decision and moves the posting to `status`.
+    pub async fn moderate(
+        pool: &PgPool,
+        job_id: &i32,
+        status: &str,
+        reason: Option<&str>,
+        moderator_id: &i32,
+    ) -> Result<Self, Error> {
+        let mut tx = pool.begin().await?;
+
+        let job = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET moderation_status = $1,
+                    published_at = CASE WHEN $1 = 'approved' AND status <> 'draft'
+                                        THEN COALESCE(published_at, now() AT TIME ZONE 'UTC')
+                                        ELSE published_at END,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $2 AND deleted_at IS NULL
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            status,
+            job_id
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        ModerationEvent::record(&mut tx, job_id, status, reason, &[], Some(*moderator_id)).await?;
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    pub async fn close(pool: &PgPool, job_id: &i32) -> Result<Self, Error> {
+        let job = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET status = 'closed',
+                    extend_token = NULL,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND deleted_at IS NULL
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
             job_id
         )
         .fetch_one(pool)
@@ -126,11 +1011,15 @@ impl Job {
         Ok(job)
     }
 
+    /// Hides the job everywhere while keeping its applications. The row is
+    /// only removed for good by `purge_deleted` once the retention window has
+    /// passed.
     pub async fn delete(pool: &PgPool, job_id: &i32) -> Result<(), Error> {
         query!(
             r#"
-                DELETE FROM jobs
-                WHERE id = $1
+                UPDATE jobs
+                SET deleted_at = (now() AT TIME ZONE 'UTC')
+                WHERE id = $1 AND deleted_at IS NULL
             "#,
             job_id
         )
@@ -138,4 +1027,37 @@ impl Job {
         .await?;
         Ok(())
     }
+
+    pub async fn restore(pool: &PgPool, job_id: &i32) -> Result<Self, Error> {
+        let job = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET deleted_at = NULL
+                WHERE id = $1 AND deleted_at IS NOT NULL
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            job_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    /// Hard-deletes jobs soft-deleted before `deleted_before`, along with
+    /// their applications.
+    pub async fn purge_deleted(pool: &PgPool, deleted_before: NaiveDateTime) -> Result<u64, Error> {
+        let result = query!(
+            r#"
+                DELETE FROM jobs
+                WHERE deleted_at < $1
+            "#,
+            deleted_before
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as};
+
+#[derive(Debug)]
+pub struct JobEvent {
+    pub job_id: i32,
+    pub kind: &'static str,
+    pub visitor_key: String,
+    pub occurred_on: NaiveDate,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct DailyJobStats {
+    pub date: NaiveDate,
+    pub views: i64,
+    pub apply_clicks: i64,
+    pub applications: i64,
+    pub featured_impressions: i64,
+    pub featured_clicks: i64,
+}
+
+impl JobEvent {
+    /// Inserts a batch of events, silently skipping ones already recorded for
+    /// the same visitor on the same day.
+    pub async fn insert_batch(pool: &PgPool, events: &[JobEvent]) -> Result<(), Error> {
+        let job_ids: Vec<i32> = events.iter().map(|event| event.job_id).collect();
+        let kinds: Vec<String> = events.iter().map(|event| event.kind.to_string()).collect();
+        let visitor_keys: Vec<String> = events
+            .iter()
+            .map(|event| event.visitor_key.clone())
+            .collect();
+        let dates: Vec<NaiveDate> = events.iter().map(|event| event.occurred_on).collect();
+
+        query!(
+            r#"
+                INSERT INTO job_events (job_id, kind, visitor_key, occurred_on)
+                SELECT e.job_id, e.kind, e.visitor_key, e.occurred_on
+                FROM UNNEST($1::int4[], $2::text[], $3::text[], $4::date[])
+                    AS e(job_id, kind, visitor_key, occurred_on)
+                WHERE EXISTS (SELECT 1 FROM jobs WHERE jobs.id = e.job_id)
+                ON CONFLICT DO NOTHING
+            "#,
+            &job_ids,
+            &kinds,
+            &visitor_keys,
+            &dates
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn daily_stats(
+        pool: &PgPool,
+        job_id: &i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyJobStats>, Error> {
+        let stats = query_as!(
+            DailyJobStats,
+            r#"
+                SELECT day::date AS "date!",
+                       (SELECT COUNT(*) FROM job_events e
+                        WHERE e.job_id = $1 AND e.kind = 'view' AND e.occurred_on = day::date) AS "views!",
+                       (SELECT COUNT(*) FROM job_events e
+                        WHERE e.job_id = $1 AND e.kind = 'apply_click' AND e.occurred_on = day::date) AS "apply_clicks!",
+                       (SELECT COUNT(*) FROM applications a
+                        WHERE a.job_id = $1 AND a.created_at::date = day::date) AS "applications!",
+                       (SELECT COUNT(*) FROM job_events e
+                        WHERE e.job_id = $1 AND e.kind = 'featured_impression' AND e.occurred_on = day::date) AS "featured_impressions!",
+                       (SELECT COUNT(*) FROM job_events e
+                        WHERE e.job_id = $1 AND e.kind = 'featured_click' AND e.occurred_on = day::date) AS "featured_clicks!"
+                FROM generate_series($2::date, $3::date, INTERVAL '1 day') AS day
+                ORDER BY day
+            "#,
+            job_id,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(stats)
+    }
+}
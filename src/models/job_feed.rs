@@ -0,0 +1,123 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query_as, query_scalar, types::Decimal};
+
+/// A live, approved posting with the company and category details feeds publish.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct FeedJob {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub salary: Decimal,
+    pub category_id: i32,
+    pub category_name: String,
+    pub company_id: i32,
+    pub company_name: String,
+    pub company_website: Option<String>,
+    pub company_logo_url: Option<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl FeedJob {
+    /// The posting if it is open and not yet expired.
+    pub async fn find_by_id(pool: &PgPool, job_id: &i32) -> Result<Self, Error> {
+        let job = query_as!(
+            FeedJob,
+            r#"
+                SELECT j.id, j.title, j.description, j.location, j.country, j.region, j.city, j.postal_code, j.latitude, j.longitude, j.salary,
+                       j.category_id, c.name AS category_name, j.company_id, co.name AS company_name, co.website AS company_website, co.logo_url AS company_logo_url,
+                       j.expires_at, j.created_at, j.updated_at
+                FROM jobs j
+                JOIN categories c ON c.id = j.category_id
+                JOIN companies co ON co.id = j.company_id
+                WHERE j.id = $1
+                  AND j.status = 'open'
+                  AND j.moderation_status = 'approved'
+                  AND j.deleted_at IS NULL
+                  AND (j.expires_at IS NULL OR j.expires_at > (now() AT TIME ZONE 'UTC'))
+            "#,
+            job_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    /// Live postings, newest first, optionally limited to a category and its
+    /// descendants.
+    pub async fn find_recent(
+        pool: &PgPool,
+        category_id: Option<i32>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, Error> {
+        let jobs = query_as!(
+            FeedJob,
+            r#"
+                SELECT j.id, j.title, j.description, j.location, j.country, j.region, j.city, j.postal_code, j.latitude, j.longitude, j.salary,
+                       j.category_id, c.name AS category_name, j.company_id, co.name AS company_name, co.website AS company_website, co.logo_url AS company_logo_url,
+                       j.expires_at, j.created_at, j.updated_at
+                FROM jobs j
+                JOIN categories c ON c.id = j.category_id
+                JOIN companies co ON co.id = j.company_id
+                WHERE j.status = 'open'
+                  AND j.moderation_status = 'approved'
+                  AND j.deleted_at IS NULL
+                  AND (j.expires_at IS NULL OR j.expires_at > (now() AT TIME ZONE 'UTC'))
+                  AND ($1::int4 IS NULL OR j.category_id IN (
+                      WITH RECURSIVE subtree AS (
+                          SELECT id FROM categories WHERE id = $1
+                          UNION ALL
+                          SELECT c.id FROM categories c JOIN subtree s ON c.parent_id = s.id
+                      )
+                      SELECT id FROM subtree
+                  ))
+                ORDER BY j.created_at DESC, j.id DESC
+                LIMIT $2 OFFSET $3
+            "#,
+            category_id,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    pub async fn count(pool: &PgPool, category_id: Option<i32>) -> Result<i64, Error> {
+        let count = query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM jobs j
+                WHERE j.status = 'open'
+                  AND j.moderation_status = 'approved'
+                  AND j.deleted_at IS NULL
+                  AND (j.expires_at IS NULL OR j.expires_at > (now() AT TIME ZONE 'UTC'))
+                  AND ($1::int4 IS NULL OR j.category_id IN (
+                      WITH RECURSIVE subtree AS (
+                          SELECT id FROM categories WHERE id = $1
+                          UNION ALL
+                          SELECT c.id FROM categories c JOIN subtree s ON c.parent_id = s.id
+                      )
+                      SELECT id FROM subtree
+                  ))
+            "#,
+            category_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+}
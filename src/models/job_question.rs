@@ -0,0 +1,260 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, Postgres, Transaction, query, query_as};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct JobQuestion {
+    pub id: i32,
+    pub job_id: i32,
+    pub position: i32,
+    pub prompt: String,
+    pub kind: String,
+    pub required: bool,
+    pub options: Vec<String>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub max_length: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+pub struct JobQuestionInput {
+    /// Set to keep an existing question, and the rules on it, in place.
+    pub id: Option<i32>,
+    pub prompt: String,
+    pub kind: String,
+    pub required: bool,
+    pub options: Vec<String>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub max_length: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ApplicationAnswer {
+    pub question_id: Option<i32>,
+    pub prompt: String,
+    pub kind: String,
+    pub answer: String,
+}
+
+impl JobQuestion {
+    pub async fn find_by_job_id(pool: &PgPool, job_id: &i32) -> Result<Vec<Self>, Error> {
+        let questions = query_as!(
+            JobQuestion,
+            r#"
+                SELECT id, job_id, position, prompt, kind, required, options, min_value, max_value, max_length, created_at
+                FROM job_questions
+                WHERE job_id = $1
+                ORDER BY position
+            "#,
+            job_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(questions)
+    }
+
+    /// Replaces the job's questions with `questions`, in order. Questions
+    /// with an id are updated in place and everything else is recreated, so
+    /// knock-out rules survive unless their question is removed, changes kind
+    /// or drops the option the rule compares against. Answers to removed
+    /// questions keep their copy of the prompt.
+    pub async fn set_for_job(
+        pool: &PgPool,
+        job_id: &i32,
+        questions: &[JobQuestionInput],
+    ) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+
+        let kept: Vec<i32> = questions
+            .iter()
+            .filter_map(|question| question.id)
+            .collect();
+        query!(
+            "DELETE FROM job_questions WHERE job_id = $1 AND id <> ALL($2)",
+            job_id,
+            &kept
+        )
+        .execute(&mut tx)
+        .await?;
+
+        for (position, question) in questions.iter().enumerate() {
+            match question.id {
+                Some(question_id) => {
+                    query!(
+                        r#"
+                            DELETE FROM knockout_rules
+                            WHERE question_id = $1
+                              AND (NOT EXISTS (SELECT 1 FROM job_questions WHERE id = $1 AND kind = $2)
+                                   OR ($2 = 'multiple_choice' AND value <> ALL($3)))
+                        "#,
+                        question_id,
+                        question.kind,
+                        &question.options
+                    )
+                    .execute(&mut tx)
+                    .await?;
+
+                    query!(
+                        r#"
+                            UPDATE job_questions
+                            SET position = $1,
+                                prompt = $2,
+                                kind = $3,
+                                required = $4,
+                                options = $5,
+                                min_value = $6,
+                                max_value = $7,
+                                max_length = $8
+                            WHERE id = $9 AND job_id = $10
+                        "#,
+                        position as i32,
+                        question.prompt,
+                        question.kind,
+                        question.required,
+                        &question.options,
+                        question.min_value,
+                        question.max_value,
+                        question.max_length,
+                        question_id,
+                        job_id
+                    )
+                    .execute(&mut tx)
+                    .await?;
+                }
+                None => {
+                    query!(
+                        r#"
+                            INSERT INTO job_questions (job_id, position, prompt, kind, required, options, min_value, max_value, max_length)
+                            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                        "#,
+                        job_id,
+                        position as i32,
+                        question.prompt,
+                        question.kind,
+                        question.required,
+                        &question.options,
+                        question.min_value,
+                        question.max_value,
+                        question.max_length
+                    )
+                    .execute(&mut tx)
+                    .await?;
+                }
+            }
+        }
+
+        tx.commit().await
+    }
+}
+
+impl ApplicationAnswer {
+    pub async fn find_by_application_id(
+        pool: &PgPool,
+        application_id: &i32,
+    ) -> Result<Vec<Self>, Error> {
+        let answers = query_as!(
+            ApplicationAnswer,
+            r#"
+                SELECT question_id, prompt, kind, answer
+                FROM application_answers
+                WHERE application_id = $1
+                ORDER BY id
+            "#,
+            application_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(answers)
+    }
+
+    pub async fn insert_batch(
+        tx: &mut Transaction<'_, Postgres>,
+        application_id: &i32,
+        answers: &[ApplicationAnswer],
+    ) -> Result<(), Error> {
+        let mut question_ids = Vec::with_capacity(answers.len());
+        let mut prompts = Vec::with_capacity(answers.len());
+        let mut kinds = Vec::with_capacity(answers.len());
+        let mut values = Vec::with_capacity(answers.len());
+        // Fresh answers always point at a live question.
+        for answer in answers {
+            if let Some(question_id) = answer.question_id {
+                question_ids.push(question_id);
+                prompts.push(answer.prompt.clone());
+                kinds.push(answer.kind.clone());
+                values.push(answer.answer.clone());
+            }
+        }
+
+        query!(
+            r#"
+                INSERT INTO application_answers (application_id, question_id, prompt, kind, answer)
+                SELECT $1::int4, a.question_id, a.prompt, a.kind, a.answer
+                FROM UNNEST($2::int4[], $3::text[], $4::text[], $5::text[])
+                    AS a(question_id, prompt, kind, answer)
+            "#,
+            application_id,
+            &question_ids,
+            &prompts,
+            &kinds,
+            &values
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::{Executor, query_scalar};
+
+    fn multiple_choice(id: i32, options: &[&str]) -> JobQuestionInput {
+        JobQuestionInput {
+            id: Some(id),
+            prompt: "Preferred stack".to_string(),
+            kind: "multiple_choice".to_string(),
+            required: true,
+            options: options.iter().map(|option| option.to_string()).collect(),
+            min_value: None,
+            max_value: None,
+            max_length: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn removing_an_option_drops_the_rules_that_require_it(pool: PgPool) {
+        pool.execute(
+            r#"
+                INSERT INTO users (id, username, email, password_hash, role)
+                VALUES (1, 'recruiter', 'recruiter@example.com', '', 'employer');
+                INSERT INTO categories (id, name, slug) VALUES (1, 'Engineering', 'engineering');
+                INSERT INTO companies (id, name) VALUES (1, 'Acme');
+                INSERT INTO jobs (id, title, description, location, category_id, company_id, employer_id)
+                VALUES (1, 'Engineer', 'Build things', 'Remote', 1, 1, 1);
+                INSERT INTO job_questions (id, job_id, position, prompt, kind, required, options)
+                VALUES (1, 1, 0, 'Preferred stack', 'multiple_choice', true, ARRAY['rust', 'go', 'java']);
+                INSERT INTO knockout_rules (job_id, question_id, operator, value)
+                VALUES (1, 1, 'eq', 'rust'), (1, 1, 'neq', 'java');
+            "#,
+        )
+        .await
+        .unwrap();
+
+        JobQuestion::set_for_job(&pool, &1, &[multiple_choice(1, &["rust", "go"])])
+            .await
+            .unwrap();
+
+        let values: Vec<String> =
+            query_scalar("SELECT value FROM knockout_rules WHERE question_id = 1 ORDER BY value")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(values, vec!["rust".to_string()]);
+    }
+}
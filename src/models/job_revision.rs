@@ -0,0 +1,88 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, Postgres, Transaction, query, query_as, types::Decimal};
+
+/// A snapshot of a posting's editable fields as of one job version.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct JobRevision {
+    pub id: i32,
+    pub job_id: i32,
+    pub version: i32,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub salary: Decimal,
+    pub category_id: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub author_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl JobRevision {
+    /// Snapshots the job's current row. Called in the same transaction as the
+    /// write that produced it, so history and the posting never disagree.
+    pub async fn record(
+        tx: &mut Transaction<'_, Postgres>,
+        job_id: &i32,
+        author_id: Option<&i32>,
+    ) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO job_revisions (job_id, version, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, expires_at, author_id)
+                SELECT id, version, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, expires_at, $2
+                FROM jobs
+                WHERE id = $1
+            "#,
+            job_id,
+            author_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_job_id(pool: &PgPool, job_id: &i32) -> Result<Vec<Self>, Error> {
+        let revisions = query_as!(
+            JobRevision,
+            r#"
+                SELECT id, job_id, version, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, expires_at, author_id, created_at
+                FROM job_revisions
+                WHERE job_id = $1
+                ORDER BY version DESC
+            "#,
+            job_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(revisions)
+    }
+
+    pub async fn find_by_version(
+        pool: &PgPool,
+        job_id: &i32,
+        version: &i32,
+    ) -> Result<Self, Error> {
+        let revision = query_as!(
+            JobRevision,
+            r#"
+                SELECT id, job_id, version, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, expires_at, author_id, created_at
+                FROM job_revisions
+                WHERE job_id = $1 AND version = $2
+            "#,
+            job_id,
+            version
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(revision)
+    }
+}
@@ -0,0 +1,154 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as, types::Decimal};
+
+/// A company's reusable starting point for postings. `title`, `description`
+/// and `location` may contain `{{placeholders}}`.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct JobTemplate {
+    pub id: i32,
+    pub company_id: i32,
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub salary: Decimal,
+    pub category_id: i32,
+    pub language: String,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+pub struct JobTemplateInput {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub salary: Decimal,
+    pub category_id: i32,
+    pub language: String,
+}
+
+impl JobTemplate {
+    pub async fn create(
+        pool: &PgPool,
+        company_id: &i32,
+        input: &JobTemplateInput,
+        created_by: &i32,
+    ) -> Result<Self, Error> {
+        let template = query_as!(
+            JobTemplate,
+            r#"
+                INSERT INTO job_templates (company_id, name, title, description, location, salary, category_id, language, created_by)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING id, company_id, name, title, description, location, salary, category_id, language, created_by, created_at, updated_at
+            "#,
+            company_id,
+            input.name,
+            input.title,
+            input.description,
+            input.location,
+            input.salary,
+            input.category_id,
+            input.language,
+            created_by
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    pub async fn find_by_id(
+        pool: &PgPool,
+        company_id: &i32,
+        template_id: &i32,
+    ) -> Result<Self, Error> {
+        let template = query_as!(
+            JobTemplate,
+            r#"
+                SELECT id, company_id, name, title, description, location, salary, category_id, language, created_by, created_at, updated_at
+                FROM job_templates
+                WHERE id = $1 AND company_id = $2
+            "#,
+            template_id,
+            company_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    pub async fn find_by_company_id(pool: &PgPool, company_id: &i32) -> Result<Vec<Self>, Error> {
+        let templates = query_as!(
+            JobTemplate,
+            r#"
+                SELECT id, company_id, name, title, description, location, salary, category_id, language, created_by, created_at, updated_at
+                FROM job_templates
+                WHERE company_id = $1
+                ORDER BY name
+            "#,
+            company_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(templates)
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        company_id: &i32,
+        template_id: &i32,
+        input: &JobTemplateInput,
+    ) -> Result<Self, Error> {
+        let template = query_as!(
+            JobTemplate,
+            r#"
+                UPDATE job_templates
+                SET name = $1,
+                    title = $2,
+                    description = $3,
+                    location = $4,
+                    salary = $5,
+                    category_id = $6,
+                    language = $7,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $8 AND company_id = $9
+                RETURNING id, company_id, name, title, description, location, salary, category_id, language, created_by, created_at, updated_at
+            "#,
+            input.name,
+            input.title,
+            input.description,
+            input.location,
+            input.salary,
+            input.category_id,
+            input.language,
+            template_id,
+            company_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    /// Returns whether a template was deleted. Drafts made from it keep their
+    /// content and lose the reference.
+    pub async fn delete(pool: &PgPool, company_id: &i32, template_id: &i32) -> Result<bool, Error> {
+        let result = query!(
+            r#"
+                DELETE FROM job_templates
+                WHERE id = $1 AND company_id = $2
+            "#,
+            template_id,
+            company_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
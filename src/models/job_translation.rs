@@ -0,0 +1,110 @@
+use crate::models::job::Job;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as};
+
+/// A posting's title and description in a language other than its own.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct JobTranslation {
+    pub job_id: i32,
+    pub locale: String,
+    pub title: String,
+    pub description: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl JobTranslation {
+    pub async fn find_by_job_id(pool: &PgPool, job_id: &i32) -> Result<Vec<Self>, Error> {
+        let translations = query_as!(
+            JobTranslation,
+            r#"
+                SELECT job_id, locale, title, description, created_at, updated_at
+                FROM job_translations
+                WHERE job_id = $1
+                ORDER BY locale
+            "#,
+            job_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(translations)
+    }
+
+    /// Translations of any of `job_ids` whose primary language is one of
+    /// `languages`.
+    pub async fn find_for_jobs(
+        pool: &PgPool,
+        job_ids: &[i32],
+        languages: &[String],
+    ) -> Result<Vec<Self>, Error> {
+        let translations = query_as!(
+            JobTranslation,
+            r#"
+                SELECT job_id, locale, title, description, created_at, updated_at
+                FROM job_translations
+                WHERE job_id = ANY($1) AND split_part(locale, '-', 1) = ANY($2)
+            "#,
+            job_ids,
+            languages
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(translations)
+    }
+
+    /// Adds or replaces the translation. An approved posting goes back to
+    /// review under the same rules as an edit.
+    pub async fn upsert(
+        pool: &PgPool,
+        job_id: &i32,
+        locale: &str,
+        title: &str,
+        description: &str,
+        flags: &[String],
+    ) -> Result<Self, Error> {
+        let mut tx = pool.begin().await?;
+
+        let translation = query_as!(
+            JobTranslation,
+            r#"
+                INSERT INTO job_translations (job_id, locale, title, description)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (job_id, locale) DO UPDATE
+                SET title = EXCLUDED.title,
+                    description = EXCLUDED.description,
+                    updated_at = CURRENT_TIMESTAMP
+                RETURNING job_id, locale, title, description, created_at, updated_at
+            "#,
+            job_id,
+            locale,
+            title,
+            description
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        Job::review_edit(&mut tx, job_id, flags).await?;
+        tx.commit().await?;
+
+        Ok(translation)
+    }
+
+    /// Returns whether a translation was deleted.
+    pub async fn delete(pool: &PgPool, job_id: &i32, locale: &str) -> Result<bool, Error> {
+        let result = query!(
+            r#"
+                DELETE FROM job_translations
+                WHERE job_id = $1 AND locale = $2
+            "#,
+            job_id,
+            locale
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,254 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, Postgres, Transaction, query, query_as, query_scalar};
+
+/// Recorded as the reason on status changes made by a failed rule.
+pub const KNOCKOUT_REASON: &str = "Failed a knock-out rule";
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct KnockoutRule {
+    pub id: i32,
+    pub job_id: i32,
+    pub question_id: i32,
+    pub prompt: String,
+    pub kind: String,
+    pub operator: String,
+    pub value: String,
+    pub reject_delay_minutes: i32,
+    pub created_at: NaiveDateTime,
+}
+
+pub struct KnockoutRuleInput {
+    pub question_id: i32,
+    pub operator: String,
+    pub value: String,
+    pub reject_delay_minutes: i32,
+}
+
+/// The result of checking one rule against one application.
+pub struct RuleOutcome {
+    pub rule_id: i32,
+    pub description: String,
+    pub answer: Option<String>,
+    pub passed: bool,
+    pub reject_delay_minutes: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RuleEvaluation {
+    pub rule_id: Option<i32>,
+    pub description: String,
+    pub answer: Option<String>,
+    pub passed: bool,
+    pub evaluated_at: NaiveDateTime,
+}
+
+impl KnockoutRule {
+    pub fn evaluate(&self, answer: Option<&str>) -> RuleOutcome {
+        RuleOutcome {
+            rule_id: self.id,
+            description: self.describe(),
+            answer: answer.map(str::to_string),
+            passed: self.passes(answer),
+            reject_delay_minutes: self.reject_delay_minutes,
+        }
+    }
+
+    /// Whether `answer` satisfies the rule. Numeric questions compare as
+    /// numbers, everything else as text, and a missing answer never passes.
+    pub fn passes(&self, answer: Option<&str>) -> bool {
+        let Some(answer) = answer else {
+            return false;
+        };
+
+        if self.kind == "numeric" {
+            let (Ok(answer), Ok(value)) = (answer.parse::<f64>(), self.value.parse::<f64>()) else {
+                return false;
+            };
+            return match self.operator.as_str() {
+                "eq" => answer == value,
+                "neq" => answer != value,
+                "gt" => answer > value,
+                "gte" => answer >= value,
+                "lt" => answer < value,
+                "lte" => answer <= value,
+                _ => false,
+            };
+        }
+
+        match self.operator.as_str() {
+            "eq" => answer == self.value,
+            "neq" => answer != self.value,
+            _ => false,
+        }
+    }
+
+    /// Human-readable form of the rule, e.g. `Years of experience >= 3`.
+    pub fn describe(&self) -> String {
+        let operator = match self.operator.as_str() {
+            "eq" => "=",
+            "neq" => "!=",
+            "gt" => ">",
+            "gte" => ">=",
+            "lt" => "<",
+            "lte" => "<=",
+            other => other,
+        };
+        format!("{} {} {}", self.prompt, operator, self.value)
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        job_id: &i32,
+        input: &KnockoutRuleInput,
+    ) -> Result<Self, Error> {
+        let rule_id = query_scalar!(
+            r#"
+                INSERT INTO knockout_rules (job_id, question_id, operator, value, reject_delay_minutes)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id
+            "#,
+            job_id,
+            input.question_id,
+            input.operator,
+            input.value,
+            input.reject_delay_minutes
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Self::find_by_id(pool, &rule_id).await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, rule_id: &i32) -> Result<Self, Error> {
+        let rule = query_as!(
+            KnockoutRule,
+            r#"
+                SELECT r.id, r.job_id, r.question_id, q.prompt, q.kind, r.operator, r.value, r.reject_delay_minutes, r.created_at
+                FROM knockout_rules r
+                JOIN job_questions q ON q.id = r.question_id
+                WHERE r.id = $1
+            "#,
+            rule_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(rule)
+    }
+
+    pub async fn find_by_job_id(pool: &PgPool, job_id: &i32) -> Result<Vec<Self>, Error> {
+        let rules = query_as!(
+            KnockoutRule,
+            r#"
+                SELECT r.id, r.job_id, r.question_id, q.prompt, q.kind, r.operator, r.value, r.reject_delay_minutes, r.created_at
+                FROM knockout_rules r
+                JOIN job_questions q ON q.id = r.question_id
+                WHERE r.job_id = $1
+                ORDER BY r.id
+            "#,
+            job_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn delete(pool: &PgPool, rule_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                DELETE FROM knockout_rules
+                WHERE id = $1
+            "#,
+            rule_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Answers to `question_id` from the job's applications at `status`,
+    /// with `None` for applicants who left it unanswered.
+    pub async fn pending_answers(
+        pool: &PgPool,
+        job_id: &i32,
+        question_id: &i32,
+        status: &str,
+    ) -> Result<Vec<Option<String>>, Error> {
+        let answers = query_scalar!(
+            r#"
+                SELECT aa.answer AS "answer?"
+                FROM applications a
+                LEFT JOIN application_answers aa ON aa.application_id = a.id AND aa.question_id = $2
+                WHERE a.job_id = $1 AND a.status = $3
+            "#,
+            job_id,
+            question_id,
+            status
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(answers)
+    }
+}
+
+impl RuleEvaluation {
+    pub async fn record(
+        tx: &mut Transaction<'_, Postgres>,
+        application_id: &i32,
+        outcomes: &[RuleOutcome],
+    ) -> Result<(), Error> {
+        let rule_ids: Vec<i32> = outcomes.iter().map(|outcome| outcome.rule_id).collect();
+        let descriptions: Vec<String> = outcomes
+            .iter()
+            .map(|outcome| outcome.description.clone())
+            .collect();
+        // Answers are never stored empty, so '' stands in for "unanswered".
+        let answers: Vec<String> = outcomes
+            .iter()
+            .map(|outcome| outcome.answer.clone().unwrap_or_default())
+            .collect();
+        let passed: Vec<bool> = outcomes.iter().map(|outcome| outcome.passed).collect();
+
+        query!(
+            r#"
+                INSERT INTO rule_evaluations (application_id, rule_id, description, answer, passed)
+                SELECT $1::int4, e.rule_id, e.description, NULLIF(e.answer, ''), e.passed
+                FROM UNNEST($2::int4[], $3::text[], $4::text[], $5::bool[])
+                    AS e(rule_id, description, answer, passed)
+            "#,
+            application_id,
+            &rule_ids,
+            &descriptions,
+            &answers,
+            &passed
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_application_id(
+        pool: &PgPool,
+        application_id: &i32,
+    ) -> Result<Vec<Self>, Error> {
+        let evaluations = query_as!(
+            RuleEvaluation,
+            r#"
+                SELECT rule_id, description, answer, passed, evaluated_at
+                FROM rule_evaluations
+                WHERE application_id = $1
+                ORDER BY id
+            "#,
+            application_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(evaluations)
+    }
+}
@@ -1,3 +1,20 @@
 pub mod application;
+pub mod application_status_change;
+pub mod category;
+pub mod company;
+pub mod featured_placement;
 pub mod job;
+pub mod job_event;
+pub mod job_feed;
+pub mod job_question;
+pub mod job_revision;
+pub mod job_template;
+pub mod job_translation;
+pub mod knockout_rule;
+pub mod moderation_event;
+pub mod notification;
+pub mod pipeline_stage;
+pub mod saved_job;
+pub mod saved_search;
+pub mod skill;
 pub mod user;
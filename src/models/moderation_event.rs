@@ -0,0 +1,120 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, Postgres, Transaction, query, query_as, query_scalar};
+
+/// One step in a posting's review: a submission, with the automatic checks'
+/// flags, or a moderator's decision with its reason.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ModerationEvent {
+    pub id: i32,
+    pub job_id: i32,
+    pub action: String,
+    pub reason: Option<String>,
+    pub flags: Vec<String>,
+    pub moderator_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A posting waiting for review, with what got it there.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ModerationQueueItem {
+    pub job_id: i32,
+    pub title: String,
+    pub company_id: i32,
+    pub company_name: String,
+    pub company_verified: bool,
+    pub employer_id: Option<i32>,
+    pub flags: Vec<String>,
+    pub submitted_at: NaiveDateTime,
+}
+
+impl ModerationEvent {
+    pub async fn record(
+        tx: &mut Transaction<'_, Postgres>,
+        job_id: &i32,
+        action: &str,
+        reason: Option<&str>,
+        flags: &[String],
+        moderator_id: Option<i32>,
+    ) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO moderation_events (job_id, action, reason, flags, moderator_id)
+                VALUES ($1, $2, $3, $4, $5)
+            "#,
+            job_id,
+            action,
+            reason,
+            flags,
+            moderator_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_job_id(pool: &PgPool, job_id: &i32) -> Result<Vec<Self>, Error> {
+        let events = query_as!(
+            ModerationEvent,
+            r#"
+                SELECT id, job_id, action, reason, flags, moderator_id, created_at
+                FROM moderation_events
+                WHERE job_id = $1
+                ORDER BY created_at, id
+            "#,
+            job_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    /// Pending postings, longest waiting first.
+    pub async fn find_queue(
+        pool: &PgPool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ModerationQueueItem>, Error> {
+        let items = query_as!(
+            ModerationQueueItem,
+            r#"
+                SELECT j.id AS job_id, j.title, j.company_id, c.name AS company_name, c.verified AS company_verified, j.employer_id,
+                       COALESCE(e.flags, '{}') AS "flags!", COALESCE(e.created_at, j.updated_at) AS "submitted_at!"
+                FROM jobs j
+                JOIN companies c ON c.id = j.company_id
+                LEFT JOIN LATERAL (
+                    SELECT flags, created_at
+                    FROM moderation_events
+                    WHERE job_id = j.id AND action = 'submitted'
+                    ORDER BY created_at DESC, id DESC
+                    LIMIT 1
+                ) e ON TRUE
+                WHERE j.moderation_status = 'pending' AND j.status <> 'draft' AND j.deleted_at IS NULL
+                ORDER BY 8, j.id
+                LIMIT $1 OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    pub async fn count_queue(pool: &PgPool) -> Result<i64, Error> {
+        let count = query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM jobs
+                WHERE moderation_status = 'pending' AND status <> 'draft' AND deleted_at IS NULL
+            "#
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Notification {
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: String,
+    pub message: String,
+    pub job_id: Option<i32>,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl Notification {
+    pub async fn find_by_user_id(
+        pool: &PgPool,
+        user_id: &i32,
+        unread_only: bool,
+    ) -> Result<Vec<Self>, Error> {
+        let notifications = query_as!(
+            Notification,
+            r#"
+                SELECT id, user_id, kind, message, job_id, read_at, created_at
+                FROM notifications
+                WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+                ORDER BY created_at DESC
+            "#,
+            user_id,
+            unread_only
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(notifications)
+    }
+
+    pub async fn mark_read(
+        pool: &PgPool,
+        user_id: &i32,
+        notification_id: &i32,
+    ) -> Result<Self, Error> {
+        let notification = query_as!(
+            Notification,
+            r#"
+                UPDATE notifications
+                SET read_at = COALESCE(read_at, CURRENT_TIMESTAMP)
+                WHERE id = $1 AND user_id = $2
+                RETURNING id, user_id, kind, message, job_id, read_at, created_at
+            "#,
+            notification_id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(notification)
+    }
+
+    pub async fn mark_all_read(pool: &PgPool, user_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                UPDATE notifications
+                SET read_at = CURRENT_TIMESTAMP
+                WHERE user_id = $1 AND read_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        user_id: &i32,
+        kind: &str,
+        job_id: Option<i32>,
+        message: &str,
+    ) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO notifications (user_id, kind, job_id, message)
+                VALUES ($1, $2, $3, $4)
+            "#,
+            user_id,
+            kind,
+            job_id,
+            message
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Tells everyone who saved the job that it is no longer accepting applications.
+    pub async fn notify_job_closed(pool: &PgPool, job_id: &i32, title: &str) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO notifications (user_id, kind, job_id, message)
+                SELECT user_id, 'saved_job_closed', job_id, 'A job you saved, "' || $2 || '", has been closed'
+                FROM saved_jobs
+                WHERE job_id = $1
+            "#,
+            job_id,
+            title
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Notifies seekers whose saved jobs expire within `within_hours`, once per
+    /// saved job. Claiming rows with an UPDATE keeps concurrent runs from
+    /// notifying twice.
+    pub async fn notify_saved_jobs_expiring(
+        pool: &PgPool,
+        within_hours: i32,
+    ) -> Result<u64, Error> {
+        let result = query!(
+            r#"
+                WITH due AS (
+                    UPDATE saved_jobs s
+                    SET expiry_notified_at = CURRENT_TIMESTAMP
+                    FROM jobs j
+                    WHERE j.id = s.job_id
+                      AND j.status = 'open'
+                      AND j.deleted_at IS NULL
+                      AND j.expires_at > (now() AT TIME ZONE 'UTC')
+                      AND j.expires_at <= (now() AT TIME ZONE 'UTC') + make_interval(hours => $1)
+                      AND s.expiry_notified_at IS NULL
+                    RETURNING s.user_id, s.job_id, j.title, j.expires_at
+                )
+                INSERT INTO notifications (user_id, kind, job_id, message)
+                SELECT user_id, 'saved_job_expiring', job_id,
+                       'A job you saved, "' || title || '", closes on ' || to_char(expires_at, 'YYYY-MM-DD')
+                FROM due
+            "#,
+            within_hours
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
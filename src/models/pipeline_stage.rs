@@ -0,0 +1,137 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as, query_scalar};
+
+/// The stage knock-out rules move failed applicants to. Every pipeline has it.
+pub const REJECTED_STAGE: &str = "rejected";
+
+/// One step of a hiring pipeline. `company_id` is empty for the default
+/// pipeline. Applications store the stage's `key` as their status.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct PipelineStage {
+    pub id: i32,
+    pub company_id: Option<i32>,
+    pub key: String,
+    pub name: String,
+    pub position: i32,
+    pub terminal: bool,
+    pub created_at: NaiveDateTime,
+}
+
+pub struct PipelineStageInput {
+    pub key: String,
+    pub name: String,
+    pub terminal: bool,
+}
+
+/// Applications only move forward, may jump straight to any terminal stage
+/// (rejected, withdrawn, ...) and never leave one.
+pub fn can_transition(stages: &[PipelineStage], from_status: &str, to_status: &str) -> bool {
+    let from = stages.iter().find(|stage| stage.key == from_status);
+    let to = stages.iter().find(|stage| stage.key == to_status);
+    match (from, to) {
+        (Some(from), Some(to)) => !from.terminal && (to.terminal || to.position > from.position),
+        _ => false,
+    }
+}
+
+impl PipelineStage {
+    /// The company's own stages, or the default pipeline if it has none, in
+    /// order. The first stage is where new applications start.
+    pub async fn find_for_company(pool: &PgPool, company_id: &i32) -> Result<Vec<Self>, Error> {
+        let stages = query_as!(
+            PipelineStage,
+            r#"
+                SELECT id, company_id, key, name, position, terminal, created_at
+                FROM pipeline_stages
+                WHERE COALESCE(company_id, 0) = COALESCE((SELECT company_id FROM pipeline_stages WHERE company_id = $1 LIMIT 1), 0)
+                ORDER BY position
+            "#,
+            company_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(stages)
+    }
+
+    pub async fn find_default(pool: &PgPool) -> Result<Vec<Self>, Error> {
+        let stages = query_as!(
+            PipelineStage,
+            r#"
+                SELECT id, company_id, key, name, position, terminal, created_at
+                FROM pipeline_stages
+                WHERE company_id IS NULL
+                ORDER BY position
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(stages)
+    }
+
+    /// Replaces the company's pipeline with `stages`, in order.
+    pub async fn set_for_company(
+        pool: &PgPool,
+        company_id: &i32,
+        stages: &[PipelineStageInput],
+    ) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+
+        query!(
+            "DELETE FROM pipeline_stages WHERE company_id = $1",
+            company_id
+        )
+        .execute(&mut tx)
+        .await?;
+
+        for (position, stage) in stages.iter().enumerate() {
+            query!(
+                r#"
+                    INSERT INTO pipeline_stages (company_id, key, name, position, terminal)
+                    VALUES ($1, $2, $3, $4, $5)
+                "#,
+                company_id,
+                stage.key,
+                stage.name,
+                position as i32,
+                stage.terminal
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Drops the company's own stages so it falls back to the default pipeline.
+    pub async fn reset_for_company(pool: &PgPool, company_id: &i32) -> Result<(), Error> {
+        query!(
+            "DELETE FROM pipeline_stages WHERE company_id = $1",
+            company_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Every status currently held by an application to one of the company's jobs.
+    pub async fn statuses_in_use(pool: &PgPool, company_id: &i32) -> Result<Vec<String>, Error> {
+        let statuses = query_scalar!(
+            r#"
+                SELECT DISTINCT a.status
+                FROM applications a
+                JOIN jobs j ON j.id = a.job_id
+                WHERE j.company_id = $1
+                ORDER BY a.status
+            "#,
+            company_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(statuses)
+    }
+}
@@ -0,0 +1,107 @@
+use crate::models::job::Job;
+use sqlx::{Error, PgPool, query, query_as, query_scalar};
+
+pub struct SavedJob;
+
+impl SavedJob {
+    pub async fn save(pool: &PgPool, user_id: &i32, job_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO saved_jobs (user_id, job_id)
+                VALUES ($1, $2)
+                ON CONFLICT (user_id, job_id) DO NOTHING
+            "#,
+            user_id,
+            job_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn unsave(pool: &PgPool, user_id: &i32, job_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                DELETE FROM saved_jobs
+                WHERE user_id = $1 AND job_id = $2
+            "#,
+            user_id,
+            job_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Saved jobs of a user that are still public, most recently saved first.
+    pub async fn find_jobs_by_user_id(
+        pool: &PgPool,
+        user_id: &i32,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Job>, Error> {
+        let jobs = query_as!(
+            Job,
+            r#"
+                SELECT j.id, j.title, j.description, j.location, j.country, j.region, j.city, j.postal_code, j.latitude, j.longitude, j.salary, j.category_id, j.company_id, j.employer_id, j.status, j.moderation_status, j.language, j.source_job_id, j.template_id, j.publish_at, j.expires_at, j.external_ref, j.version, j.created_at, j.updated_at
+                FROM saved_jobs s
+                JOIN jobs j ON j.id = s.job_id
+                WHERE s.user_id = $1
+                  AND j.moderation_status = 'approved'
+                  AND j.status <> 'draft'
+                  AND j.deleted_at IS NULL
+                ORDER BY s.created_at DESC
+                LIMIT $2 OFFSET $3
+            "#,
+            user_id,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    pub async fn count_by_user_id(pool: &PgPool, user_id: &i32) -> Result<i64, Error> {
+        let count = query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM saved_jobs s
+                JOIN jobs j ON j.id = s.job_id
+                WHERE s.user_id = $1
+                  AND j.moderation_status = 'approved'
+                  AND j.status <> 'draft'
+                  AND j.deleted_at IS NULL
+            "#,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Returns which of `job_ids` the user has saved.
+    pub async fn find_saved_job_ids(
+        pool: &PgPool,
+        user_id: &i32,
+        job_ids: &[i32],
+    ) -> Result<Vec<i32>, Error> {
+        let saved = query_scalar!(
+            r#"
+                SELECT job_id
+                FROM saved_jobs
+                WHERE user_id = $1 AND job_id = ANY($2)
+            "#,
+            user_id,
+            job_ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(saved)
+    }
+}
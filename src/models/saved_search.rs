@@ -0,0 +1,289 @@
+use crate::models::job::JobSearch;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Error, FromRow, PgPool, Postgres, Transaction, query, query_as, query_scalar, types::Decimal,
+};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct SavedSearch {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub q: Option<String>,
+    pub category_id: Option<i32>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub min_salary: Option<Decimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub radius_km: Option<f64>,
+    pub frequency: String,
+    pub active: bool,
+    pub unsubscribe_token: String,
+    pub last_run_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A saved search whose digest is due, with the address to deliver it to.
+#[derive(Debug, FromRow)]
+pub struct DueSavedSearch {
+    pub id: i32,
+    pub email: String,
+    pub username: String,
+    pub name: String,
+    pub q: Option<String>,
+    pub category_id: Option<i32>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub min_salary: Option<Decimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub radius_km: Option<f64>,
+    pub unsubscribe_token: String,
+    pub last_run_at: NaiveDateTime,
+}
+
+impl DueSavedSearch {
+    pub fn search(&self) -> JobSearch {
+        JobSearch {
+            q: self.q.clone(),
+            category_id: self.category_id,
+            city: self.city.clone(),
+            country: self.country.clone(),
+            min_salary: self.min_salary,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            radius_km: self.radius_km,
+        }
+    }
+}
+
+impl SavedSearch {
+    pub async fn create(
+        pool: &PgPool,
+        user_id: &i32,
+        name: &str,
+        search: &JobSearch,
+        frequency: &str,
+    ) -> Result<Self, Error> {
+        let saved_search = query_as!(
+            SavedSearch,
+            r#"
+                INSERT INTO saved_searches (user_id, name, q, category_id, city, country, min_salary, latitude, longitude, radius_km, frequency)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                RETURNING id, user_id, name, q, category_id, city, country, min_salary, latitude, longitude, radius_km, frequency, active, unsubscribe_token, last_run_at, created_at, updated_at
+            "#,
+            user_id,
+            name,
+            search.q,
+            search.category_id,
+            search.city,
+            search.country,
+            search.min_salary,
+            search.latitude,
+            search.longitude,
+            search.radius_km,
+            frequency
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(saved_search)
+    }
+
+    pub async fn find_by_id(pool: &PgPool, saved_search_id: &i32) -> Result<Self, Error> {
+        let saved_search = query_as!(
+            SavedSearch,
+            r#"
+                SELECT id, user_id, name, q, category_id, city, country, min_salary, latitude, longitude, radius_km, frequency, active, unsubscribe_token, last_run_at, created_at, updated_at
+                FROM saved_searches
+                WHERE id = $1
+            "#,
+            saved_search_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(saved_search)
+    }
+
+    pub async fn find_by_user_id(pool: &PgPool, user_id: &i32) -> Result<Vec<Self>, Error> {
+        let saved_searches = query_as!(
+            SavedSearch,
+            r#"
+                SELECT id, user_id, name, q, category_id, city, country, min_salary, latitude, longitude, radius_km, frequency, active, unsubscribe_token, last_run_at, created_at, updated_at
+                FROM saved_searches
+                WHERE user_id = $1
+                ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(saved_searches)
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        saved_search_id: &i32,
+        name: &str,
+        search: &JobSearch,
+        frequency: &str,
+        active: bool,
+    ) -> Result<Self, Error> {
+        let saved_search = query_as!(
+            SavedSearch,
+            r#"
+                UPDATE saved_searches
+                SET name = $1,
+                    q = $2,
+                    category_id = $3,
+                    city = $4,
+                    country = $5,
+                    min_salary = $6,
+                    latitude = $7,
+                    longitude = $8,
+                    radius_km = $9,
+                    frequency = $10,
+                    active = $11,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $12
+                RETURNING id, user_id, name, q, category_id, city, country, min_salary, latitude, longitude, radius_km, frequency, active, unsubscribe_token, last_run_at, created_at, updated_at
+            "#,
+            name,
+            search.q,
+            search.category_id,
+            search.city,
+            search.country,
+            search.min_salary,
+            search.latitude,
+            search.longitude,
+            search.radius_km,
+            frequency,
+            active,
+            saved_search_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(saved_search)
+    }
+
+    pub async fn unsubscribe(pool: &PgPool, token: &str) -> Result<Self, Error> {
+        let saved_search = query_as!(
+            SavedSearch,
+            r#"
+                UPDATE saved_searches
+                SET active = FALSE,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE unsubscribe_token = $1
+                RETURNING id, user_id, name, q, category_id, city, country, min_salary, latitude, longitude, radius_km, frequency, active, unsubscribe_token, last_run_at, created_at, updated_at
+            "#,
+            token
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(saved_search)
+    }
+
+    pub async fn delete(pool: &PgPool, saved_search_id: &i32) -> Result<(), Error> {
+        query!(
+            r#"
+                DELETE FROM saved_searches
+                WHERE id = $1
+            "#,
+            saved_search_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Up to `limit` users with at least one active search whose digest is
+    /// due at `now`, longest-waiting first so no one is starved. Nothing is
+    /// locked; `claim_due` does that per user.
+    pub async fn find_due_user_ids(
+        pool: &PgPool,
+        now: NaiveDateTime,
+        limit: i64,
+    ) -> Result<Vec<i32>, Error> {
+        let user_ids = query_scalar!(
+            r#"
+                SELECT s.user_id
+                FROM saved_searches s
+                JOIN users u ON u.id = s.user_id
+                WHERE s.active
+                  AND u.deleted_at IS NULL
+                  AND ((s.frequency = 'instant' AND s.last_run_at <= $1::timestamp - INTERVAL '1 minute')
+                        OR (s.frequency = 'daily' AND s.last_run_at <= $1::timestamp - INTERVAL '1 day')
+                        OR (s.frequency = 'weekly' AND s.last_run_at <= $1::timestamp - INTERVAL '7 days'))
+                GROUP BY s.user_id
+                ORDER BY MIN(s.last_run_at), s.user_id
+                LIMIT $2
+            "#,
+            now,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// Locks the user's active searches whose digest is due at `now`.
+    /// `SKIP LOCKED` lets several server instances share the work without
+    /// sending the same digest twice.
+    pub async fn claim_due(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: &i32,
+        now: NaiveDateTime,
+    ) -> Result<Vec<DueSavedSearch>, Error> {
+        let due = query_as!(
+            DueSavedSearch,
+            r#"
+                SELECT s.id, u.email, u.username, s.name, s.q, s.category_id, s.city, s.country, s.min_salary, s.latitude, s.longitude, s.radius_km, s.unsubscribe_token, s.last_run_at
+                FROM saved_searches s
+                JOIN users u ON u.id = s.user_id
+                WHERE s.user_id = $2
+                  AND s.active
+                  AND u.deleted_at IS NULL
+                  AND ((s.frequency = 'instant' AND s.last_run_at <= $1::timestamp - INTERVAL '1 minute')
+                        OR (s.frequency = 'daily' AND s.last_run_at <= $1::timestamp - INTERVAL '1 day')
+                        OR (s.frequency = 'weekly' AND s.last_run_at <= $1::timestamp - INTERVAL '7 days'))
+                ORDER BY s.id
+                FOR UPDATE OF s SKIP LOCKED
+            "#,
+            now,
+            user_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        Ok(due)
+    }
+
+    pub async fn mark_run(
+        tx: &mut Transaction<'_, Postgres>,
+        saved_search_ids: &[i32],
+        run_at: NaiveDateTime,
+    ) -> Result<(), Error> {
+        query!(
+            r#"
+                UPDATE saved_searches
+                SET last_run_at = $2
+                WHERE id = ANY($1)
+            "#,
+            saved_search_ids,
+            run_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+}
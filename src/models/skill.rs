@@ -0,0 +1,259 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, FromRow, PgPool, query, query_as};
+
+const REQUIRED_SKILL_WEIGHT: f64 = 2.0;
+const NICE_TO_HAVE_SKILL_WEIGHT: f64 = 1.0;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Skill {
+    pub id: i32,
+    pub name: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct JobSkill {
+    pub skill_id: i32,
+    pub name: String,
+    pub required: bool,
+}
+
+/// Per-job tally of how a seeker's skills line up with a job's skills.
+#[derive(Debug, FromRow)]
+pub struct SkillOverlap {
+    pub job_id: i32,
+    pub required_matched: i64,
+    pub required_total: i64,
+    pub nice_matched: i64,
+    pub nice_total: i64,
+    pub matched_skills: Vec<String>,
+    pub missing_skills: Vec<String>,
+}
+
+impl SkillOverlap {
+    /// Weighted share of the job's skills the seeker has, between 0 and 1.
+    pub fn score(&self) -> f64 {
+        let matched = self.required_matched as f64 * REQUIRED_SKILL_WEIGHT
+            + self.nice_matched as f64 * NICE_TO_HAVE_SKILL_WEIGHT;
+        let total = self.required_total as f64 * REQUIRED_SKILL_WEIGHT
+            + self.nice_total as f64 * NICE_TO_HAVE_SKILL_WEIGHT;
+        if total == 0.0 { 0.0 } else { matched / total }
+    }
+}
+
+/// Escapes `LIKE` wildcards so user input only ever matches literally.
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+pub struct ResolvedSkill {
+    pub input: String,
+    pub skill_id: Option<i32>,
+}
+
+impl Skill {
+    /// Inserts a skill or returns the existing one with the same case-insensitive
+    /// name, leaving its spelling as it was.
+    pub async fn upsert(pool: &PgPool, name: &str) -> Result<Self, Error> {
+        let skill = query_as!(
+            Skill,
+            r#"
+                INSERT INTO skills (name)
+                VALUES ($1)
+                ON CONFLICT ((lower(name))) DO UPDATE SET name = skills.name
+                RETURNING id, name, created_at
+            "#,
+            name
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(skill)
+    }
+
+    pub async fn add_alias(pool: &PgPool, skill_id: &i32, alias: &str) -> Result<(), Error> {
+        query!(
+            r#"
+                INSERT INTO skill_aliases (alias, skill_id)
+                VALUES (lower($1), $2)
+                ON CONFLICT (alias) DO NOTHING
+            "#,
+            alias.trim(),
+            skill_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn search(pool: &PgPool, term: &str) -> Result<Vec<Self>, Error> {
+        let skills = query_as!(
+            Skill,
+            r#"
+                SELECT id, name, created_at
+                FROM skills
+                WHERE $1 = ''
+                   OR name ILIKE $1 || '%'
+                   OR id IN (SELECT skill_id FROM skill_aliases WHERE alias LIKE lower($1) || '%')
+                ORDER BY name
+            "#,
+            escape_like(term.trim())
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(skills)
+    }
+
+    /// Maps free-text skill names onto the vocabulary by exact name first and
+    /// alias second, both case-insensitive. Unknown names come back with no id.
+    pub async fn resolve(pool: &PgPool, names: &[String]) -> Result<Vec<ResolvedSkill>, Error> {
+        let resolved = query_as!(
+            ResolvedSkill,
+            r#"
+                SELECT n.input AS "input!",
+                       COALESCE(
+                           (SELECT id FROM skills WHERE lower(name) = lower(trim(n.input))),
+                           (SELECT skill_id FROM skill_aliases WHERE alias = lower(trim(n.input)))
+                       ) AS skill_id
+                FROM UNNEST($1::text[]) AS n(input)
+            "#,
+            names
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(resolved)
+    }
+
+    pub async fn find_by_job_id(pool: &PgPool, job_id: &i32) -> Result<Vec<JobSkill>, Error> {
+        let skills = query_as!(
+            JobSkill,
+            r#"
+                SELECT s.id AS skill_id, s.name, js.required
+                FROM job_skills js
+                JOIN skills s ON s.id = js.skill_id
+                WHERE js.job_id = $1
+                ORDER BY js.required DESC, s.name
+            "#,
+            job_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(skills)
+    }
+
+    pub async fn set_for_job(
+        pool: &PgPool,
+        job_id: &i32,
+        required: &[i32],
+        nice_to_have: &[i32],
+    ) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+
+        query!("DELETE FROM job_skills WHERE job_id = $1", job_id)
+            .execute(&mut tx)
+            .await?;
+
+        query!(
+            r#"
+                INSERT INTO job_skills (job_id, skill_id, required)
+                SELECT $1::int4, skill_id, TRUE FROM UNNEST($2::int4[]) AS skill_id
+                UNION
+                SELECT $1, skill_id, FALSE FROM UNNEST($3::int4[]) AS skill_id
+                WHERE skill_id <> ALL($2::int4[])
+                ON CONFLICT DO NOTHING
+            "#,
+            job_id,
+            required,
+            nice_to_have
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await
+    }
+
+    pub async fn find_by_user_id(pool: &PgPool, user_id: &i32) -> Result<Vec<Self>, Error> {
+        let skills = query_as!(
+            Skill,
+            r#"
+                SELECT s.id, s.name, s.created_at
+                FROM user_skills us
+                JOIN skills s ON s.id = us.skill_id
+                WHERE us.user_id = $1
+                ORDER BY s.name
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(skills)
+    }
+
+    pub async fn set_for_user(
+        pool: &PgPool,
+        user_id: &i32,
+        skill_ids: &[i32],
+    ) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+
+        query!("DELETE FROM user_skills WHERE user_id = $1", user_id)
+            .execute(&mut tx)
+            .await?;
+
+        query!(
+            r#"
+                INSERT INTO user_skills (user_id, skill_id)
+                SELECT $1::int4, skill_id FROM UNNEST($2::int4[]) AS skill_id
+                ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            skill_ids
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await
+    }
+
+    /// Tallies skill overlap between a user and every open, unexpired job
+    /// sharing at least one of their skills.
+    pub async fn overlap_for_user(
+        pool: &PgPool,
+        user_id: &i32,
+    ) -> Result<Vec<SkillOverlap>, Error> {
+        let overlaps = query_as!(
+            SkillOverlap,
+            r#"
+                SELECT js.job_id,
+                       COUNT(us.skill_id) FILTER (WHERE js.required) AS "required_matched!",
+                       COUNT(*) FILTER (WHERE js.required) AS "required_total!",
+                       COUNT(us.skill_id) FILTER (WHERE NOT js.required) AS "nice_matched!",
+                       COUNT(*) FILTER (WHERE NOT js.required) AS "nice_total!",
+                       COALESCE(array_agg(s.name ORDER BY s.name) FILTER (WHERE us.skill_id IS NOT NULL), '{}') AS "matched_skills!",
+                       COALESCE(array_agg(s.name ORDER BY s.name) FILTER (WHERE us.skill_id IS NULL), '{}') AS "missing_skills!"
+                FROM job_skills js
+                JOIN jobs j ON j.id = js.job_id
+                JOIN skills s ON s.id = js.skill_id
+                LEFT JOIN user_skills us ON us.skill_id = js.skill_id AND us.user_id = $1
+                WHERE j.status = 'open'
+                  AND j.deleted_at IS NULL
+                  AND (j.expires_at IS NULL OR j.expires_at > (now() AT TIME ZONE 'UTC'))
+                GROUP BY js.job_id
+                HAVING COUNT(us.skill_id) > 0
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(overlaps)
+    }
+}
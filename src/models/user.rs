@@ -1,3 +1,4 @@
+use crate::models::job::Job;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::{Error, FromRow, PgPool, query, query_as};
@@ -44,7 +45,7 @@ impl User {
             r#"
                 SELECT id, username, email, password_hash, role,  created_at, updated_at
                 FROM users
-                WHERE email = $1
+                WHERE email = $1 AND deleted_at IS NULL
             "#,
             email
         )
@@ -59,7 +60,7 @@ impl User {
             r#"
                 SELECT id, username, email, password_hash, role, created_at, updated_at
                 FROM users
-                WHERE id = $1
+                WHERE id = $1 AND deleted_at IS NULL
             "#,
             user_id
         )
@@ -68,17 +69,130 @@ impl User {
 
         Ok(user)
     }
-    pub async fn delete(pool: &PgPool, user_id: i32) -> Result<(), Error> {
+    /// Hides the account and blocks sign-in; `purge_deleted` removes it for
+    /// good once the retention window has passed. Their open postings are
+    /// closed and their scheduled drafts unscheduled, so nothing of theirs
+    /// stays or goes live; the closed postings are returned.
+    pub async fn delete(pool: &PgPool, user_id: i32) -> Result<Vec<Job>, Error> {
+        let mut tx = pool.begin().await?;
+
         query!(
             r#"
-                DELETE FROM users
-                WHERE id = $1
+                UPDATE users
+                SET deleted_at = (now() AT TIME ZONE 'UTC')
+                WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(&mut tx)
+        .await?;
+
+        let closed = query_as!(
+            Job,
+            r#"
+                UPDATE jobs
+                SET status = 'closed',
+                    extend_token = NULL,
+                    version = version + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE employer_id = $1 AND status = 'open' AND deleted_at IS NULL
+                RETURNING id, title, description, location, country, region, city, postal_code, latitude, longitude, salary, category_id, company_id, employer_id, status, moderation_status, language, source_job_id, template_id, publish_at, expires_at, external_ref, version, created_at, updated_at
+            "#,
+            user_id
+        )
+        .fetch_all(&mut tx)
+        .await?;
+
+        query!(
+            r#"
+                UPDATE jobs
+                SET publish_at = NULL
+                WHERE employer_id = $1 AND status = 'draft' AND publish_at IS NOT NULL
+            "#,
+            user_id
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(closed)
+    }
+    pub async fn restore(pool: &PgPool, user_id: &i32) -> Result<Self, Error> {
+        let user = query_as!(
+            User,
+            r#"
+                UPDATE users
+                SET deleted_at = NULL
+                WHERE id = $1 AND deleted_at IS NOT NULL
+                RETURNING id, username, email, password_hash, role, created_at, updated_at
             "#,
             user_id
         )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    pub async fn purge_deleted(pool: &PgPool, deleted_before: NaiveDateTime) -> Result<u64, Error> {
+        let result = query!(
+            r#"
+                DELETE FROM users
+                WHERE deleted_at < $1
+            "#,
+            deleted_before
+        )
         .execute(pool)
         .await?;
 
-        Ok(())
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use sqlx::{Executor, Row};
+
+    #[sqlx::test]
+    async fn purging_a_recruiter_keeps_their_jobs_and_applications(pool: PgPool) {
+        pool.execute(
+            r#"
+                INSERT INTO users (id, username, email, password_hash, role)
+                VALUES (1, 'recruiter', 'recruiter@example.com', '', 'employer'),
+                       (2, 'seeker', 'seeker@example.com', '', 'job_seeker');
+                INSERT INTO categories (id, name, slug) VALUES (1, 'Engineering', 'engineering');
+                INSERT INTO companies (id, name) VALUES (1, 'Acme');
+                INSERT INTO company_members (company_id, user_id, role) VALUES (1, 1, 'owner');
+                INSERT INTO jobs (id, title, description, location, category_id, company_id, employer_id, status, moderation_status)
+                VALUES (1, 'Engineer', 'Build things', 'Remote', 1, 1, 1, 'open', 'approved');
+                INSERT INTO applications (job_id, user_id, message) VALUES (1, 2, 'Hello');
+            "#,
+        )
+        .await
+        .unwrap();
+
+        User::delete(&pool, 1).await.unwrap();
+        let purged = User::purge_deleted(&pool, Utc::now().naive_utc() + Duration::days(1))
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+
+        let row = sqlx::query(
+            r#"
+                SELECT j.employer_id, count(a.id) AS applications
+                FROM jobs j
+                LEFT JOIN applications a ON a.job_id = j.id
+                WHERE j.id = 1
+                GROUP BY j.employer_id
+            "#,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(row.get::<Option<i32>, _>("employer_id"), None);
+        assert_eq!(row.get::<i64, _>("applications"), 1);
     }
 }
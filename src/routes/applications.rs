@@ -1,17 +1,80 @@
 use crate::handlers::applications::{
-    CreateApplicationRequest, UpdateApplicationRequest, create_application, delete_application, get_application_by_id, get_applications, update_application,
+    CreateApplicationRequest, PatchApplicationRequest, UpdateApplicationRequest,
+    UpdateApplicationStatusRequest, create_application, delete_application, get_application_by_id,
+    get_application_status_history, get_applications, patch_application, update_application,
+    update_application_status,
 };
+use crate::handlers::knockout_rules::get_rule_evaluations;
 use actix_web::{
     HttpRequest, HttpResponse,
-    web::{Data, Json, Path, ServiceConfig, delete, get, post, put, scope},
+    web::{Data, Json, Path, ServiceConfig, delete, get, patch, post, put, scope},
 };
 use sqlx::PgPool;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/api/applications")
-            .route("", get().to(get_applications))
-            .route("/{id}", get().to(get_application_by_id))
+            .route(
+                "",
+                get().to(|req: HttpRequest, pool: Data<PgPool>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_applications(pool, token).await
+                }),
+            )
+            .route(
+                "/{id}",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, application_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_application_by_id(pool, application_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/evaluations",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, application_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_rule_evaluations(pool, application_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/status",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<UpdateApplicationStatusRequest>,
+                     pool: Data<PgPool>,
+                     application_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_application_status(pool, application_id, form, token, req).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/status-history",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, application_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_application_status_history(pool, application_id, token).await
+                    },
+                ),
+            )
             .route(
                 "",
                 post().to(
@@ -37,7 +100,22 @@ pub fn config(cfg: &mut ServiceConfig) {
                             Some(header) => header.to_str().unwrap_or("").to_string(),
                             None => return HttpResponse::Unauthorized().json("Missing token"),
                         };
-                        update_application(pool, application_id, form, token).await
+                        update_application(pool, application_id, form, token, req).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}",
+                patch().to(
+                    |req: HttpRequest,
+                     form: Json<PatchApplicationRequest>,
+                     pool: Data<PgPool>,
+                     application_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        patch_application(pool, application_id, form, token, req).await
                     },
                 ),
             )
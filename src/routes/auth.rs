@@ -7,4 +7,4 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("login", web::post().to(login))
             .route("register", web::post().to(register)),
     );
-}
\ No newline at end of file
+}
@@ -0,0 +1,56 @@
+use crate::handlers::categories::{
+    CreateCategoryRequest, UpdateCategoryRequest, create_category, delete_category, get_categories,
+    get_category_by_id, update_category,
+};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, Path, ServiceConfig, delete, get, post, put, scope},
+};
+use sqlx::PgPool;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/api/categories")
+            .route("", get().to(get_categories))
+            .route("/{id}", get().to(get_category_by_id))
+            .route(
+                "",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<CreateCategoryRequest>,
+                     pool: Data<PgPool>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        create_category(pool, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<UpdateCategoryRequest>,
+                     pool: Data<PgPool>,
+                     category_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_category(pool, category_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}",
+                delete().to(|req: HttpRequest, pool: Data<PgPool>, category_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    delete_category(pool, category_id, token).await
+                })
+            ),
+    );
+}
@@ -0,0 +1,254 @@
+use crate::handlers::companies::{
+    AddMemberRequest, CompanyRequest, UpdateMemberRequest, add_company_member, create_company,
+    get_company_by_id, get_company_drafts, get_company_members, get_jobs_of_company,
+    remove_company_member, update_company, update_company_member,
+};
+use crate::handlers::job_templates::{
+    InstantiateTemplateRequest, JobTemplateRequest, create_job_from_template, create_job_template,
+    delete_job_template, get_job_template, get_job_templates, update_job_template,
+};
+use crate::handlers::moderation::{VerificationRequest, set_company_verification};
+use crate::handlers::pipelines::{
+    PipelineRequest, get_company_pipeline, reset_company_pipeline, set_company_pipeline,
+};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, Path, ServiceConfig, delete, get, post, put, scope},
+};
+use sqlx::PgPool;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/api/companies")
+            .route("/{id}", get().to(get_company_by_id))
+            .route("/{id}/jobs", get().to(get_jobs_of_company))
+            .route(
+                "",
+                post().to(
+                    |req: HttpRequest, form: Json<CompanyRequest>, pool: Data<PgPool>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        create_company(pool, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<CompanyRequest>,
+                     pool: Data<PgPool>,
+                     company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_company(pool, company_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/verification",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<VerificationRequest>,
+                     pool: Data<PgPool>,
+                     company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        set_company_verification(pool, company_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/drafts",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_company_drafts(pool, company_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/pipeline",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_company_pipeline(pool, company_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/pipeline",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<PipelineRequest>,
+                     pool: Data<PgPool>,
+                     company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        set_company_pipeline(pool, company_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/pipeline",
+                delete().to(
+                    |req: HttpRequest, pool: Data<PgPool>, company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        reset_company_pipeline(pool, company_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/job-templates",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_job_templates(pool, company_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/job-templates",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<JobTemplateRequest>,
+                     pool: Data<PgPool>,
+                     company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        create_job_template(pool, company_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/job-templates/{template_id}",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_job_template(pool, path, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/job-templates/{template_id}",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<JobTemplateRequest>,
+                     pool: Data<PgPool>,
+                     path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_job_template(pool, path, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/job-templates/{template_id}",
+                delete().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        delete_job_template(pool, path, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/job-templates/{template_id}/jobs",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<InstantiateTemplateRequest>,
+                     pool: Data<PgPool>,
+                     path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        create_job_from_template(pool, path, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/members",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_company_members(pool, company_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/members",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<AddMemberRequest>,
+                     pool: Data<PgPool>,
+                     company_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        add_company_member(pool, company_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/members/{user_id}",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<UpdateMemberRequest>,
+                     pool: Data<PgPool>,
+                     path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_company_member(pool, path, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/members/{user_id}",
+                delete().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        remove_company_member(pool, path, token).await
+                    },
+                ),
+            ),
+    );
+}
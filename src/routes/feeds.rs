@@ -0,0 +1,11 @@
+use crate::handlers::feeds::{get_aggregator_feed, get_atom_feed, get_rss_feed};
+use actix_web::web::{ServiceConfig, get, scope};
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/api/feeds")
+            .route("/jobs.rss", get().to(get_rss_feed))
+            .route("/jobs.atom", get().to(get_atom_feed))
+            .route("/jobs.xml", get().to(get_aggregator_feed)),
+    );
+}
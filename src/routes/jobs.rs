@@ -1,17 +1,456 @@
+use crate::handlers::applications::{ApplicationFilter, get_job_applications};
+use crate::handlers::featured_placements::{
+    FeaturedPlacementRequest, create_featured_placement, delete_featured_placement,
+    get_featured_placements, record_featured_click,
+};
+use crate::handlers::feeds::get_job_jsonld;
+use crate::handlers::job_imports::{ImportQuery, MAX_IMPORT_BYTES, import_jobs};
+use crate::handlers::job_questions::{
+    UpdateJobQuestionsRequest, get_job_questions, update_job_questions,
+};
+use crate::handlers::job_revisions::{
+    DiffQuery, get_job_revision, get_job_revision_diff, get_job_revisions, restore_job_revision,
+};
+use crate::handlers::job_translations::{
+    TranslationRequest, delete_job_translation, get_job_translations, put_job_translation,
+};
 use crate::handlers::jobs::{
-    CreateJobRequest, UpdateJobRequest, create_job, delete_job, get_job_by_id, get_jobs, update_job,
+    CreateJobRequest, JobFilter, PatchJobRequest, ScheduleJobRequest, StatsQuery, UpdateJobRequest,
+    clone_job, close_job, create_job, delete_job, extend_job, get_duplicate_report,
+    get_extend_confirmation, get_job_by_id, get_job_stats, get_jobs, get_recommended_jobs,
+    get_similar_jobs, patch_job, publish_job, record_apply_click, restore_job, schedule_job,
+    update_job,
+};
+use crate::handlers::knockout_rules::{
+    KnockoutRuleRequest, create_knockout_rule, delete_knockout_rule, get_knockout_rules,
+    preview_knockout_rule,
 };
+use crate::handlers::moderation::get_job_moderation;
+use crate::handlers::saved_jobs::{save_job, unsave_job};
+use crate::handlers::skills::{
+    UpdateJobSkillsRequest, get_job_matches, get_job_skills, update_job_skills,
+};
+use crate::services::analytics::AnalyticsRecorder;
+use crate::utils::pagination::Pagination;
 use actix_web::{
     HttpRequest, HttpResponse,
-    web::{Data, Json, Path, ServiceConfig, delete, get, post, put, scope},
+    web::{
+        Bytes, Data, Json, Path, PayloadConfig, Query, ServiceConfig, delete, get, patch, post,
+        put, resource, scope,
+    },
 };
 use sqlx::PgPool;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/api/jobs")
-            .route("", get().to(get_jobs))
-            .route("/{id}", get().to(get_job_by_id))
+            .route(
+                "",
+                get().to(
+                    |req: HttpRequest,
+                     pool: Data<PgPool>,
+                     analytics: Data<AnalyticsRecorder>,
+                     filter: Query<JobFilter>| async move {
+                        let token = req
+                            .headers()
+                            .get("Authorization")
+                            .map(|header| header.to_str().unwrap_or("").to_string());
+                        get_jobs(pool, analytics, filter, token, req).await
+                    },
+                ),
+            )
+            .route(
+                "/matches",
+                get().to(|req: HttpRequest, pool: Data<PgPool>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_job_matches(pool, token).await
+                }),
+            )
+            .route("/extend/{token}", get().to(get_extend_confirmation))
+            .route("/extend/{token}", post().to(extend_job))
+            .route(
+                "/recommended",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, pagination: Query<Pagination>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_recommended_jobs(pool, pagination, token, req).await
+                    },
+                ),
+            )
+            .service(
+                resource("/import")
+                    .app_data(PayloadConfig::new(MAX_IMPORT_BYTES))
+                    .route(post().to(
+                        |req: HttpRequest,
+                         body: Bytes,
+                         pool: Data<PgPool>,
+                         query: Query<ImportQuery>| async move {
+                            let token = match req.headers().get("Authorization") {
+                                Some(header) => header.to_str().unwrap_or("").to_string(),
+                                None => return HttpResponse::Unauthorized().json("Missing token"),
+                            };
+                            import_jobs(pool, query, body, token, req).await
+                        },
+                    )),
+            )
+            .route(
+                "/duplicates",
+                get().to(|req: HttpRequest, pool: Data<PgPool>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_duplicate_report(pool, token).await
+                }),
+            )
+            .route(
+                "/{id}",
+                get().to(
+                    |req: HttpRequest,
+                     pool: Data<PgPool>,
+                     analytics: Data<AnalyticsRecorder>,
+                     job_id: Path<i32>| async move {
+                        let token = req
+                            .headers()
+                            .get("Authorization")
+                            .map(|header| header.to_str().unwrap_or("").to_string());
+                        get_job_by_id(pool, analytics, job_id, token, req).await
+                    },
+                ),
+            )
+            .route("/{id}/jsonld", get().to(get_job_jsonld))
+            .route("/{id}/similar", get().to(get_similar_jobs))
+            .route("/{id}/skills", get().to(get_job_skills))
+            .route("/{id}/questions", get().to(get_job_questions))
+            .route("/{id}/apply-click", post().to(record_apply_click))
+            .route("/{id}/featured-click", post().to(record_featured_click))
+            .route(
+                "/{id}/featured",
+                get().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_featured_placements(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/featured",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<FeaturedPlacementRequest>,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        create_featured_placement(pool, job_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/featured/{placement_id}",
+                delete().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        delete_featured_placement(pool, path, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/close",
+                post().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    close_job(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/schedule",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<ScheduleJobRequest>,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        schedule_job(pool, job_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/restore",
+                post().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    restore_job(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/clone",
+                post().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    clone_job(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/publish",
+                post().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    publish_job(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/save",
+                post().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    save_job(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/save",
+                delete().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    unsave_job(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/applications",
+                get().to(
+                    |req: HttpRequest,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>,
+                     filter: Query<ApplicationFilter>,
+                     pagination: Query<Pagination>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_job_applications(pool, job_id, filter, pagination, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/stats",
+                get().to(
+                    |req: HttpRequest,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>,
+                     query: Query<StatsQuery>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_job_stats(pool, job_id, query, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/questions",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<UpdateJobQuestionsRequest>,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_job_questions(pool, job_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/rules",
+                get().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_knockout_rules(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/rules",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<KnockoutRuleRequest>,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        create_knockout_rule(pool, job_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/rules/preview",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<KnockoutRuleRequest>,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        preview_knockout_rule(pool, job_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/rules/{rule_id}",
+                delete().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        delete_knockout_rule(pool, path, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/moderation",
+                get().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_job_moderation(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/translations",
+                get().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = req
+                        .headers()
+                        .get("Authorization")
+                        .map(|header| header.to_str().unwrap_or("").to_string());
+                    get_job_translations(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/translations/{locale}",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<TranslationRequest>,
+                     pool: Data<PgPool>,
+                     path: Path<(i32, String)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        put_job_translation(pool, path, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/translations/{locale}",
+                delete().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, String)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        delete_job_translation(pool, path, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/revisions",
+                get().to(|req: HttpRequest, pool: Data<PgPool>, job_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_job_revisions(pool, job_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/revisions/diff",
+                get().to(
+                    |req: HttpRequest,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>,
+                     query: Query<DiffQuery>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_job_revision_diff(pool, job_id, query, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/revisions/{version}",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_job_revision(pool, path, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/revisions/{version}/restore",
+                post().to(
+                    |req: HttpRequest, pool: Data<PgPool>, path: Path<(i32, i32)>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        restore_job_revision(pool, path, token, req).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}/skills",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<UpdateJobSkillsRequest>,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_job_skills(pool, job_id, form, token).await
+                    },
+                ),
+            )
             .route(
                 "",
                 post().to(
@@ -37,7 +476,22 @@ pub fn config(cfg: &mut ServiceConfig) {
                             Some(header) => header.to_str().unwrap_or("").to_string(),
                             None => return HttpResponse::Unauthorized().json("Missing token"),
                         };
-                        update_job(pool, job_id, form, token).await
+                        update_job(pool, job_id, form, token, req).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}",
+                patch().to(
+                    |req: HttpRequest,
+                     form: Json<PatchJobRequest>,
+                     pool: Data<PgPool>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        patch_job(pool, job_id, form, token, req).await
                     },
                 ),
             )
@@ -1,4 +1,11 @@
 pub mod applications;
 pub mod auth;
+pub mod categories;
+pub mod companies;
+pub mod feeds;
 pub mod jobs;
-pub mod users;
\ No newline at end of file
+pub mod moderation;
+pub mod notifications;
+pub mod saved_searches;
+pub mod skills;
+pub mod users;
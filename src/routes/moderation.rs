@@ -0,0 +1,76 @@
+use crate::handlers::moderation::{
+    ModerationRequest, approve_job, get_moderation_queue, reject_job, request_job_changes,
+};
+use crate::services::mailer::Mailer;
+use crate::utils::pagination::Pagination;
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, Path, Query, ServiceConfig, get, post, scope},
+};
+use sqlx::PgPool;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/api/moderation")
+            .route(
+                "/queue",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, pagination: Query<Pagination>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_moderation_queue(pool, pagination, token).await
+                    },
+                ),
+            )
+            .route(
+                "/jobs/{id}/approve",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<ModerationRequest>,
+                     pool: Data<PgPool>,
+                     mailer: Data<Mailer>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        approve_job(pool, mailer, job_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/jobs/{id}/reject",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<ModerationRequest>,
+                     pool: Data<PgPool>,
+                     mailer: Data<Mailer>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        reject_job(pool, mailer, job_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/jobs/{id}/request-changes",
+                post().to(
+                    |req: HttpRequest,
+                     form: Json<ModerationRequest>,
+                     pool: Data<PgPool>,
+                     mailer: Data<Mailer>,
+                     job_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        request_job_changes(pool, mailer, job_id, form, token).await
+                    },
+                ),
+            ),
+    );
+}
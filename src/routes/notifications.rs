@@ -0,0 +1,50 @@
+use crate::handlers::notifications::{
+    NotificationFilter, get_notifications, mark_all_notifications_read, mark_notification_read,
+};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Path, Query, ServiceConfig, get, post, scope},
+};
+use sqlx::PgPool;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/api/notifications")
+            .route(
+                "",
+                get().to(
+                    |req: HttpRequest,
+                     pool: Data<PgPool>,
+                     filter: Query<NotificationFilter>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_notifications(pool, filter, token).await
+                    },
+                ),
+            )
+            .route(
+                "/read-all",
+                post().to(|req: HttpRequest, pool: Data<PgPool>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    mark_all_notifications_read(pool, token).await
+                }),
+            )
+            .route(
+                "/{id}/read",
+                post().to(
+                    |req: HttpRequest, pool: Data<PgPool>, notification_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        mark_notification_read(pool, notification_id, token).await
+                    },
+                ),
+            ),
+    );
+}
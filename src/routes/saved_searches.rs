@@ -0,0 +1,77 @@
+use crate::handlers::saved_searches::{
+    SavedSearchRequest, create_saved_search, delete_saved_search, get_saved_search_by_id,
+    get_saved_searches, unsubscribe_saved_search, update_saved_search,
+};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, Path, ServiceConfig, delete, get, post, put, scope},
+};
+use sqlx::PgPool;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/api/saved-searches")
+            .route(
+                "",
+                get().to(|req: HttpRequest, pool: Data<PgPool>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_saved_searches(pool, token).await
+                }),
+            )
+            .route(
+                "",
+                post().to(
+                    |req: HttpRequest, form: Json<SavedSearchRequest>, pool: Data<PgPool>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        create_saved_search(pool, form, token).await
+                    },
+                ),
+            )
+            .route("/unsubscribe/{token}", get().to(unsubscribe_saved_search))
+            .route(
+                "/{id}",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, saved_search_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_saved_search_by_id(pool, saved_search_id, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<SavedSearchRequest>,
+                     pool: Data<PgPool>,
+                     saved_search_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_saved_search(pool, saved_search_id, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/{id}",
+                delete().to(
+                    |req: HttpRequest, pool: Data<PgPool>, saved_search_id: Path<i32>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        delete_saved_search(pool, saved_search_id, token).await
+                    },
+                ),
+            ),
+    );
+}
@@ -0,0 +1,21 @@
+use crate::handlers::skills::{CreateSkillRequest, create_skill, get_skills};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, ServiceConfig, get, post, scope},
+};
+use sqlx::PgPool;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(scope("/api/skills").route("", get().to(get_skills)).route(
+        "",
+        post().to(
+            |req: HttpRequest, form: Json<CreateSkillRequest>, pool: Data<PgPool>| async move {
+                let token = match req.headers().get("Authorization") {
+                    Some(header) => header.to_str().unwrap_or("").to_string(),
+                    None => return HttpResponse::Unauthorized().json("Missing token"),
+                };
+                create_skill(pool, form, token).await
+            },
+        ),
+    ));
+}
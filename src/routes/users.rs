@@ -1,11 +1,96 @@
-use crate::handlers::users::{get_applications_of_user, get_jobs_of_user, get_user_by_id};
-use actix_web::web::{ServiceConfig, get, scope};
+use crate::handlers::companies::get_my_companies;
+use crate::handlers::saved_jobs::get_saved_jobs;
+use crate::handlers::skills::{UpdateUserSkillsRequest, get_my_skills, update_my_skills};
+use crate::handlers::users::{
+    delete_user, get_applications_of_user, get_jobs_of_user, get_user_by_id, restore_user,
+};
+use crate::utils::pagination::Pagination;
+use actix_web::{
+    HttpRequest, HttpResponse,
+    web::{Data, Json, Path, Query, ServiceConfig, delete, get, post, put, scope},
+};
+use sqlx::PgPool;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/api/users")
+            .route(
+                "/me/companies",
+                get().to(|req: HttpRequest, pool: Data<PgPool>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_my_companies(pool, token).await
+                }),
+            )
+            .route(
+                "/me/skills",
+                get().to(|req: HttpRequest, pool: Data<PgPool>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_my_skills(pool, token).await
+                }),
+            )
+            .route(
+                "/me/skills",
+                put().to(
+                    |req: HttpRequest,
+                     form: Json<UpdateUserSkillsRequest>,
+                     pool: Data<PgPool>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        update_my_skills(pool, form, token).await
+                    },
+                ),
+            )
+            .route(
+                "/me/saved-jobs",
+                get().to(
+                    |req: HttpRequest, pool: Data<PgPool>, pagination: Query<Pagination>| async move {
+                        let token = match req.headers().get("Authorization") {
+                            Some(header) => header.to_str().unwrap_or("").to_string(),
+                            None => return HttpResponse::Unauthorized().json("Missing token"),
+                        };
+                        get_saved_jobs(pool, pagination, token).await
+                    },
+                ),
+            )
             .route("/{id}", get().to(get_user_by_id))
             .route("/{id}/jobs", get().to(get_jobs_of_user))
-            .route("/{id}/applications", get().to(get_applications_of_user)),
+            .route(
+                "/{id}/applications",
+                get().to(|req: HttpRequest, pool: Data<PgPool>, user_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    get_applications_of_user(pool, user_id, token).await
+                }),
+            )
+            .route(
+                "/{id}",
+                delete().to(|req: HttpRequest, pool: Data<PgPool>, user_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    delete_user(pool, user_id, token).await
+                }),
+            )
+            .route(
+                "/{id}/restore",
+                post().to(|req: HttpRequest, pool: Data<PgPool>, user_id: Path<i32>| async move {
+                    let token = match req.headers().get("Authorization") {
+                        Some(header) => header.to_str().unwrap_or("").to_string(),
+                        None => return HttpResponse::Unauthorized().json("Missing token"),
+                    };
+                    restore_user(pool, user_id, token).await
+                }),
+            ),
     );
 }
@@ -0,0 +1,96 @@
+use crate::models::job::Job;
+use crate::models::saved_search::{DueSavedSearch, SavedSearch};
+use crate::services::mailer::Mailer;
+use chrono::{NaiveDateTime, Utc};
+use sqlx::PgPool;
+use std::env;
+
+const BATCH_SIZE: i64 = 500;
+
+/// Runs every due saved search against the jobs published since its last run
+/// and mails each user a single digest covering all of their searches.
+///
+/// Each user's digest is claimed, sent and marked as run in its own
+/// transaction, so one failure never causes other users' digests to be sent
+/// again.
+pub async fn send_due_digests(pool: &PgPool, mailer: &Mailer) -> Result<(), sqlx::Error> {
+    let now = Utc::now().naive_utc();
+    let user_ids = SavedSearch::find_due_user_ids(pool, now, BATCH_SIZE).await?;
+
+    for user_id in user_ids {
+        if let Err(error) = send_digest(pool, mailer, &user_id, now).await {
+            eprintln!("Failed to send job alert to user {user_id}: {error}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Searches are claimed with `FOR UPDATE SKIP LOCKED` and only marked as run
+/// once their digest was delivered, so a failed send is retried next tick.
+async fn send_digest(
+    pool: &PgPool,
+    mailer: &Mailer,
+    user_id: &i32,
+    now: NaiveDateTime,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let searches = SavedSearch::claim_due(&mut tx, user_id, now).await?;
+    // Another instance got there first.
+    let Some(recipient) = searches.first() else {
+        return Ok(());
+    };
+
+    let mut sections = Vec::new();
+    for saved_search in &searches {
+        let jobs = Job::search(
+            pool,
+            &saved_search.search(),
+            Some(saved_search.last_run_at),
+            Some(now),
+        )
+        .await?;
+        if !jobs.is_empty() {
+            sections.push(digest_section(saved_search, &jobs));
+        }
+    }
+
+    if !sections.is_empty() {
+        let body = format!(
+            "Hi {},\n\nNew jobs match your saved searches.\n\n{}",
+            recipient.username,
+            sections.join("\n")
+        );
+        if let Err(error) = mailer
+            .send(&recipient.email, "New jobs for your saved searches", body)
+            .await
+        {
+            eprintln!("Failed to send job alert to {}: {error}", recipient.email);
+            return Ok(());
+        }
+    }
+
+    let ids: Vec<i32> = searches
+        .iter()
+        .map(|saved_search| saved_search.id)
+        .collect();
+    SavedSearch::mark_run(&mut tx, &ids, now).await?;
+
+    tx.commit().await
+}
+
+fn digest_section(saved_search: &DueSavedSearch, jobs: &[Job]) -> String {
+    let app_url = env::var("APP_URL").unwrap_or_else(|_| "http://localhost:8000".to_string());
+    let mut section = format!("{} ({} new)\n", saved_search.name, jobs.len());
+    for job in jobs {
+        section.push_str(&format!(
+            "- {} ({}) {}/api/jobs/{}\n",
+            job.title, job.location, app_url, job.id
+        ));
+    }
+    section.push_str(&format!(
+        "Unsubscribe: {}/api/saved-searches/unsubscribe/{}\n",
+        app_url, saved_search.unsubscribe_token
+    ));
+    section
+}
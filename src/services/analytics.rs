@@ -0,0 +1,180 @@
+use crate::models::job_event::JobEvent;
+use crate::utils::jwt::validate_token;
+use actix_web::{HttpRequest, rt};
+use chrono::{NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::env;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const CHANNEL_CAPACITY: usize = 10_000;
+const BATCH_SIZE: usize = 500;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Queues job analytics events and writes them to the database in batches from
+/// a background task, so request handlers never wait on the insert.
+#[derive(Clone)]
+pub struct AnalyticsRecorder {
+    sender: mpsc::Sender<JobEvent>,
+    secret: Arc<[u8]>,
+    trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl AnalyticsRecorder {
+    /// Visitor keys are derived from `ANALYTICS_SECRET`. Without it a random
+    /// secret is used, so the same visitor counts again after a restart.
+    /// Forwarding headers are only believed from the addresses listed in
+    /// `TRUSTED_PROXIES`, comma-separated.
+    pub fn start(pool: PgPool) -> Self {
+        let secret = match env::var("ANALYTICS_SECRET") {
+            Ok(secret) => secret.into_bytes(),
+            Err(_) => {
+                eprintln!(
+                    "ANALYTICS_SECRET is not set; using a random secret, so unique visitors reset on restart"
+                );
+                rand::random::<[u8; 32]>().to_vec()
+            }
+        };
+        let trusted_proxies = env::var("TRUSTED_PROXIES")
+            .map(|proxies| {
+                proxies
+                    .split(',')
+                    .filter_map(|proxy| proxy.trim().parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        rt::spawn(run(pool, receiver));
+        AnalyticsRecorder {
+            sender,
+            secret: secret.into(),
+            trusted_proxies,
+        }
+    }
+
+    pub fn record_view(&self, job_id: i32, req: &HttpRequest) {
+        self.record(job_id, "view", req);
+    }
+
+    pub fn record_apply_click(&self, job_id: i32, req: &HttpRequest) {
+        self.record(job_id, "apply_click", req);
+    }
+
+    /// A featured slot showing the job in a listing, counted apart from views.
+    pub fn record_featured_impression(&self, job_id: i32, req: &HttpRequest) {
+        self.record(job_id, "featured_impression", req);
+    }
+
+    pub fn record_featured_click(&self, job_id: i32, req: &HttpRequest) {
+        self.record(job_id, "featured_click", req);
+    }
+
+    fn record(&self, job_id: i32, kind: &'static str, req: &HttpRequest) {
+        let occurred_on = Utc::now().date_naive();
+        let event = JobEvent {
+            job_id,
+            kind,
+            visitor_key: visitor_key(&self.secret, occurred_on, self.client_addr(req), req),
+            occurred_on,
+        };
+        // Analytics are best effort: when the queue is full the event is dropped
+        // rather than slowing down the request.
+        let _ = self.sender.try_send(event);
+    }
+
+    /// The connecting address, or the one a trusted proxy forwarded for.
+    /// Anyone else could put whatever they like in `X-Forwarded-For`.
+    fn client_addr(&self, req: &HttpRequest) -> Option<String> {
+        let peer = req.peer_addr()?.ip();
+        if self.trusted_proxies.contains(&peer)
+            && let Some(forwarded) = req.connection_info().realip_remote_addr()
+        {
+            return Some(forwarded.to_string());
+        }
+        Some(peer.to_string())
+    }
+}
+
+/// Identifies a visitor without storing personal data: signed-in users by
+/// their account, anonymous visitors by IP address and user agent, keyed with
+/// HMAC-SHA256. The key is derived from the server secret and the day, so keys
+/// cannot be brute-forced from the IP space nor linked across days.
+fn visitor_key(
+    secret: &[u8],
+    day: NaiveDate,
+    client_addr: Option<String>,
+    req: &HttpRequest,
+) -> String {
+    let mut daily = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    daily.update(day.to_string().as_bytes());
+    let daily_key = daily.finalize().into_bytes();
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(&daily_key).expect("HMAC accepts any key length");
+    let subject = req
+        .headers()
+        .get("Authorization")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|token| validate_token(token).ok())
+        .map(|claims| claims.sub);
+
+    match subject {
+        Some(email) => {
+            mac.update(b"user\0");
+            mac.update(email.as_bytes());
+        }
+        None => {
+            mac.update(b"anonymous\0");
+            mac.update(client_addr.unwrap_or_default().as_bytes());
+            mac.update(b"\0");
+            let user_agent = req
+                .headers()
+                .get("User-Agent")
+                .and_then(|header| header.to_str().ok());
+            mac.update(user_agent.unwrap_or("").as_bytes());
+        }
+    }
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+async fn run(pool: PgPool, mut receiver: mpsc::Receiver<JobEvent>) {
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Some(event) => {
+                    batch.push(event);
+                    if batch.len() >= BATCH_SIZE {
+                        flush(&pool, &mut batch).await;
+                    }
+                }
+                None => {
+                    flush(&pool, &mut batch).await;
+                    return;
+                }
+            },
+            _ = ticker.tick() => flush(&pool, &mut batch).await,
+        }
+    }
+}
+
+async fn flush(pool: &PgPool, batch: &mut Vec<JobEvent>) {
+    if batch.is_empty() {
+        return;
+    }
+    if let Err(error) = JobEvent::insert_batch(pool, batch).await {
+        eprintln!("Failed to record {} job events: {error}", batch.len());
+    }
+    batch.clear();
+}
@@ -0,0 +1,226 @@
+use crate::models::job::{Job, JobInput, JobSearch};
+use serde::Serialize;
+use sqlx::{Error, PgPool};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::hash::{Hash, Hasher};
+
+/// Words per shingle and hash functions per MinHash signature.
+const SHINGLE_SIZE: usize = 3;
+const SIGNATURE_SIZE: u64 = 64;
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicatePolicy {
+    Off,
+    Warn,
+    Block,
+}
+
+impl DuplicatePolicy {
+    /// Read from `DUPLICATE_POSTING_POLICY` (`off`, `warn` or `block`),
+    /// defaulting to `warn`.
+    pub fn from_env() -> Self {
+        match env::var("DUPLICATE_POSTING_POLICY").as_deref() {
+            Ok("off") => DuplicatePolicy::Off,
+            Ok("block") => DuplicatePolicy::Block,
+            _ => DuplicatePolicy::Warn,
+        }
+    }
+}
+
+/// The lowest description similarity, from 0 to 1, that counts as a
+/// duplicate. Read from `DUPLICATE_SIMILARITY_THRESHOLD`.
+fn similarity_threshold() -> f64 {
+    env::var("DUPLICATE_SIMILARITY_THRESHOLD")
+        .ok()
+        .and_then(|threshold| threshold.parse().ok())
+        .filter(|threshold: &f64| (0.0..=1.0).contains(threshold))
+        .unwrap_or(DEFAULT_SIMILARITY_THRESHOLD)
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateMatch {
+    pub job_id: i32,
+    pub title: String,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateCluster {
+    pub company_id: i32,
+    pub jobs: Vec<DuplicateMatch>,
+}
+
+/// Lowercases and keeps only words, so punctuation and spacing changes do not
+/// make a posting look new.
+fn normalize(text: &str) -> Vec<String> {
+    text.split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// What two postings are compared on: exact normalized title and location
+/// plus a MinHash signature estimating how similar the descriptions are.
+struct Fingerprint {
+    title: Vec<String>,
+    location: Vec<String>,
+    signature: Vec<u64>,
+}
+
+impl Fingerprint {
+    fn new(title: &str, location: &str, description: &str) -> Self {
+        let words = normalize(description);
+        let shingles: Vec<&[String]> = if words.len() < SHINGLE_SIZE {
+            vec![words.as_slice()]
+        } else {
+            words.windows(SHINGLE_SIZE).collect()
+        };
+
+        let signature = (0..SIGNATURE_SIZE)
+            .map(|seed| {
+                shingles
+                    .iter()
+                    .map(|shingle| {
+                        let mut hasher = DefaultHasher::new();
+                        seed.hash(&mut hasher);
+                        shingle.hash(&mut hasher);
+                        hasher.finish()
+                    })
+                    .min()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        Fingerprint {
+            title: normalize(title),
+            location: normalize(location),
+            signature,
+        }
+    }
+
+    fn of_job(job: &Job) -> Self {
+        Self::new(&job.title, &job.location, &job.description)
+    }
+
+    /// The estimated Jaccard similarity of the two descriptions' shingles.
+    fn similarity(&self, other: &Fingerprint) -> f64 {
+        let equal = self
+            .signature
+            .iter()
+            .zip(&other.signature)
+            .filter(|(a, b)| a == b)
+            .count();
+        equal as f64 / SIGNATURE_SIZE as f64
+    }
+
+    /// The description similarity when the postings count as duplicates.
+    fn duplicate_of(&self, other: &Fingerprint, threshold: f64) -> Option<f64> {
+        if self.title != other.title || self.location != other.location {
+            return None;
+        }
+        let similarity = self.similarity(other);
+        (similarity >= threshold).then_some(similarity)
+    }
+}
+
+/// The company's open jobs that `input` duplicates, most similar first.
+/// `job_id` is the posting being edited, which is never its own duplicate.
+pub async fn find_duplicates(
+    pool: &PgPool,
+    company_id: &i32,
+    job_id: Option<i32>,
+    input: &JobInput,
+) -> Result<Vec<DuplicateMatch>, Error> {
+    let threshold = similarity_threshold();
+    let fingerprint = Fingerprint::new(&input.title, &input.location, &input.description);
+
+    let mut matches: Vec<DuplicateMatch> = Job::find_by_company_id(pool, company_id)
+        .await?
+        .into_iter()
+        .filter(|job| job.status == "open" && Some(job.id) != job_id)
+        .filter_map(|job| {
+            let similarity = fingerprint.duplicate_of(&Fingerprint::of_job(&job), threshold)?;
+            Some(DuplicateMatch {
+                job_id: job.id,
+                title: job.title,
+                similarity,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+    Ok(matches)
+}
+
+/// Union-find lookup with path halving.
+fn root(parent: &mut [usize], mut index: usize) -> usize {
+    while parent[index] != index {
+        parent[index] = parent[parent[index]];
+        index = parent[index];
+    }
+    index
+}
+
+/// Groups every company's open jobs into clusters of mutual duplicates. A job
+/// joins a cluster if it duplicates any member, so chains of near-identical
+/// reposts end up together. Each job's similarity is to its closest match.
+pub async fn find_clusters(pool: &PgPool) -> Result<Vec<DuplicateCluster>, Error> {
+    let threshold = similarity_threshold();
+    let jobs = Job::search(pool, &JobSearch::default(), None, None).await?;
+
+    let mut by_company: BTreeMap<i32, Vec<(Job, Fingerprint)>> = BTreeMap::new();
+    for job in jobs {
+        let fingerprint = Fingerprint::of_job(&job);
+        by_company
+            .entry(job.company_id)
+            .or_default()
+            .push((job, fingerprint));
+    }
+
+    let mut clusters = Vec::new();
+    for (company_id, jobs) in by_company {
+        // Union-find over the company's jobs, by index.
+        let mut parent: Vec<usize> = (0..jobs.len()).collect();
+        let mut best: Vec<Option<f64>> = vec![None; jobs.len()];
+
+        for i in 0..jobs.len() {
+            for j in (i + 1)..jobs.len() {
+                if let Some(similarity) = jobs[i].1.duplicate_of(&jobs[j].1, threshold) {
+                    best[i] = Some(best[i].map_or(similarity, |best| best.max(similarity)));
+                    best[j] = Some(best[j].map_or(similarity, |best| best.max(similarity)));
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[a] = b;
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<DuplicateMatch>> = HashMap::new();
+        for (index, (job, _)) in jobs.iter().enumerate() {
+            if let Some(similarity) = best[index] {
+                groups
+                    .entry(root(&mut parent, index))
+                    .or_default()
+                    .push(DuplicateMatch {
+                        job_id: job.id,
+                        title: job.title.clone(),
+                        similarity,
+                    });
+            }
+        }
+
+        let mut company_clusters: Vec<DuplicateCluster> = groups
+            .into_values()
+            .map(|mut jobs| {
+                jobs.sort_by_key(|job| job.job_id);
+                DuplicateCluster { company_id, jobs }
+            })
+            .collect();
+        company_clusters.sort_by_key(|cluster| cluster.jobs[0].job_id);
+        clusters.extend(company_clusters);
+    }
+
+    Ok(clusters)
+}
@@ -0,0 +1,57 @@
+use crate::models::featured_placement::FeaturedPlacement;
+use crate::models::job::Job;
+use std::collections::HashMap;
+use std::env;
+
+/// 1-based positions in a listing reserved for featured jobs.
+const DEFAULT_FEATURED_SLOTS: &[usize] = &[1, 6, 11];
+
+/// The featured positions, read from `FEATURED_SLOTS` as a comma-separated
+/// list of 1-based positions, in ascending order.
+fn featured_slots() -> Vec<usize> {
+    let mut slots: Vec<usize> = env::var("FEATURED_SLOTS")
+        .ok()
+        .map(|slots| {
+            slots
+                .split(',')
+                .filter_map(|slot| slot.trim().parse().ok())
+                .filter(|slot| *slot > 0)
+                .collect()
+        })
+        .unwrap_or_else(|| DEFAULT_FEATURED_SLOTS.to_vec());
+    slots.sort_unstable();
+    slots.dedup();
+    slots
+}
+
+/// Moves jobs with an active placement out of the organic results and into
+/// the featured slots, strongest placement in the first slot. Each returned
+/// job is paired with whether it sits in a featured slot. Featured jobs
+/// beyond the number of slots keep their organic position, and a slot past
+/// the end of a short listing goes to the end instead.
+pub fn mix_featured(jobs: Vec<Job>, placements: &[FeaturedPlacement]) -> Vec<(Job, bool)> {
+    let slots = featured_slots();
+    let rank: HashMap<i32, usize> = placements
+        .iter()
+        .take(slots.len())
+        .enumerate()
+        .map(|(rank, placement)| (placement.job_id, rank))
+        .collect();
+
+    let mut featured: Vec<(usize, Job)> = Vec::new();
+    let mut listing: Vec<(Job, bool)> = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        match rank.get(&job.id) {
+            Some(rank) => featured.push((*rank, job)),
+            None => listing.push((job, false)),
+        }
+    }
+    featured.sort_by_key(|(rank, _)| *rank);
+
+    for ((_, job), slot) in featured.into_iter().zip(slots) {
+        let index = (slot - 1).min(listing.len());
+        listing.insert(index, (job, true));
+    }
+
+    listing
+}
@@ -0,0 +1,263 @@
+use crate::models::job_feed::FeedJob;
+use chrono::NaiveDateTime;
+use serde_json::{Map, Value, json};
+use std::env;
+
+const FEED_TITLE: &str = "Job Board";
+const DEFAULT_SALARY_CURRENCY: &str = "USD";
+
+pub fn app_url() -> String {
+    env::var("APP_URL").unwrap_or_else(|_| "http://localhost:8000".to_string())
+}
+
+fn job_url(app_url: &str, job: &FeedJob) -> String {
+    format!("{app_url}/api/jobs/{}", job.id)
+}
+
+/// Escapes text for use in XML element content and attribute values, and
+/// drops control characters XML 1.0 cannot represent at all.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(character),
+            character if character.is_control() => {}
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Stored timestamps are UTC.
+fn rfc2822(timestamp: NaiveDateTime) -> String {
+    timestamp.and_utc().to_rfc2822()
+}
+
+fn rfc3339(timestamp: NaiveDateTime) -> String {
+    timestamp.and_utc().to_rfc3339()
+}
+
+/// The feed changes whenever a listed job does, so its newest update doubles
+/// as the build date and keeps the output stable between requests.
+fn last_updated(jobs: &[FeedJob]) -> Option<NaiveDateTime> {
+    jobs.iter().map(|job| job.updated_at).max()
+}
+
+/// A `schema.org/JobPosting` document for the posting.
+pub fn job_posting_jsonld(job: &FeedJob) -> Value {
+    let app_url = app_url();
+    let currency = env::var("SALARY_CURRENCY").unwrap_or_else(|_| DEFAULT_SALARY_CURRENCY.into());
+
+    let mut address = Map::new();
+    address.insert("@type".into(), json!("PostalAddress"));
+    let parts = [
+        ("addressLocality", &job.city),
+        ("addressRegion", &job.region),
+        ("postalCode", &job.postal_code),
+        ("addressCountry", &job.country),
+    ];
+    for (key, value) in parts {
+        if let Some(value) = value {
+            address.insert(key.into(), json!(value));
+        }
+    }
+    if address.len() == 1 {
+        address.insert("streetAddress".into(), json!(job.location));
+    }
+
+    let mut place = json!({ "@type": "Place", "address": address });
+    if let (Some(latitude), Some(longitude)) = (job.latitude, job.longitude) {
+        place["geo"] = json!({
+            "@type": "GeoCoordinates",
+            "latitude": latitude,
+            "longitude": longitude,
+        });
+    }
+
+    let mut organization = json!({ "@type": "Organization", "name": job.company_name });
+    if let Some(website) = &job.company_website {
+        organization["sameAs"] = json!(website);
+    }
+    if let Some(logo_url) = &job.company_logo_url {
+        organization["logo"] = json!(logo_url);
+    }
+
+    let mut posting = json!({
+        "@context": "https://schema.org/",
+        "@type": "JobPosting",
+        "title": job.title,
+        "description": job.description,
+        "identifier": {
+            "@type": "PropertyValue",
+            "name": job.company_name,
+            "value": job.id.to_string(),
+        },
+        "url": job_url(&app_url, job),
+        "datePosted": rfc3339(job.created_at),
+        "hiringOrganization": organization,
+        "jobLocation": place,
+        "occupationalCategory": job.category_name,
+        "baseSalary": {
+            "@type": "MonetaryAmount",
+            "currency": currency,
+            "value": {
+                "@type": "QuantitativeValue",
+                "value": job.salary.to_string().parse::<f64>().unwrap_or_default(),
+                "unitText": "YEAR",
+            },
+        },
+    });
+    if let Some(expires_at) = job.expires_at {
+        posting["validThrough"] = json!(rfc3339(expires_at));
+    }
+    posting
+}
+
+/// Serializes JSON-LD so it can also be pasted into a `<script>` tag as is.
+pub fn render_jsonld(document: &Value) -> String {
+    document.to_string().replace('<', "\\u003c")
+}
+
+/// An RSS 2.0 channel of the given jobs.
+pub fn render_rss(jobs: &[FeedJob], feed_url: &str) -> String {
+    let app_url = app_url();
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n");
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(FEED_TITLE)));
+    xml.push_str(&format!("<link>{}</link>\n", escape_xml(&app_url)));
+    xml.push_str("<description>Recently posted jobs</description>\n");
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(feed_url)
+    ));
+    if let Some(updated) = last_updated(jobs) {
+        xml.push_str(&format!(
+            "<lastBuildDate>{}</lastBuildDate>\n",
+            rfc2822(updated)
+        ));
+    }
+    for job in jobs {
+        let url = escape_xml(&job_url(&app_url, job));
+        xml.push_str("<item>\n");
+        xml.push_str(&format!(
+            "<title>{} at {}</title>\n",
+            escape_xml(&job.title),
+            escape_xml(&job.company_name)
+        ));
+        xml.push_str(&format!("<link>{url}</link>\n"));
+        xml.push_str(&format!("<guid isPermaLink=\"true\">{url}</guid>\n"));
+        xml.push_str(&format!("<pubDate>{}</pubDate>\n", rfc2822(job.created_at)));
+        xml.push_str(&format!(
+            "<category>{}</category>\n",
+            escape_xml(&job.category_name)
+        ));
+        xml.push_str(&format!(
+            "<description>{}</description>\n",
+            escape_xml(&job.description)
+        ));
+        xml.push_str("</item>\n");
+    }
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// An Atom 1.0 feed of the given jobs.
+pub fn render_atom(jobs: &[FeedJob], feed_url: &str) -> String {
+    let app_url = app_url();
+    let updated = last_updated(jobs).unwrap_or_default();
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("<id>{}</id>\n", escape_xml(feed_url)));
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(FEED_TITLE)));
+    xml.push_str(&format!("<updated>{}</updated>\n", rfc3339(updated)));
+    xml.push_str(&format!(
+        "<link rel=\"self\" href=\"{}\"/>\n",
+        escape_xml(feed_url)
+    ));
+    xml.push_str(&format!("<link href=\"{}\"/>\n", escape_xml(&app_url)));
+    for job in jobs {
+        let url = escape_xml(&job_url(&app_url, job));
+        xml.push_str("<entry>\n");
+        xml.push_str(&format!("<id>{url}</id>\n"));
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&job.title)));
+        xml.push_str(&format!("<link href=\"{url}\"/>\n"));
+        xml.push_str(&format!(
+            "<published>{}</published>\n",
+            rfc3339(job.created_at)
+        ));
+        xml.push_str(&format!("<updated>{}</updated>\n", rfc3339(job.updated_at)));
+        xml.push_str(&format!(
+            "<author><name>{}</name></author>\n",
+            escape_xml(&job.company_name)
+        ));
+        xml.push_str(&format!(
+            "<category term=\"{}\"/>\n",
+            escape_xml(&job.category_name)
+        ));
+        xml.push_str(&format!(
+            "<summary type=\"text\">{}</summary>\n",
+            escape_xml(&job.description)
+        ));
+        xml.push_str("</entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// One page of jobs in the `<source><job>…</job></source>` layout most job
+/// aggregators ingest.
+pub fn render_aggregator(jobs: &[FeedJob], page: i64, total_pages: i64) -> String {
+    let app_url = app_url();
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<source>\n");
+    xml.push_str(&format!(
+        "<publisher>{}</publisher>\n",
+        escape_xml(FEED_TITLE)
+    ));
+    xml.push_str(&format!(
+        "<publisherurl>{}</publisherurl>\n",
+        escape_xml(&app_url)
+    ));
+    if let Some(updated) = last_updated(jobs) {
+        xml.push_str(&format!(
+            "<lastBuildDate>{}</lastBuildDate>\n",
+            rfc2822(updated)
+        ));
+    }
+    xml.push_str(&format!("<page>{page}</page>\n"));
+    xml.push_str(&format!("<totalPages>{total_pages}</totalPages>\n"));
+    for job in jobs {
+        xml.push_str("<job>\n");
+        let fields = [
+            ("title", Some(job.title.clone())),
+            ("date", Some(rfc2822(job.created_at))),
+            ("referencenumber", Some(job.id.to_string())),
+            ("url", Some(job_url(&app_url, job))),
+            ("company", Some(job.company_name.clone())),
+            ("city", job.city.clone()),
+            ("state", job.region.clone()),
+            ("country", job.country.clone()),
+            ("postalcode", job.postal_code.clone()),
+            ("location", Some(job.location.clone())),
+            ("description", Some(job.description.clone())),
+            ("salary", Some(job.salary.to_string())),
+            ("category", Some(job.category_name.clone())),
+            ("expirationdate", job.expires_at.map(rfc2822)),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                xml.push_str(&format!("<{name}>{}</{name}>\n", escape_xml(&value)));
+            }
+        }
+        xml.push_str("</job>\n");
+    }
+    xml.push_str("</source>\n");
+    xml
+}
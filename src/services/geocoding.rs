@@ -0,0 +1,52 @@
+use std::sync::OnceLock;
+
+const CITIES_CSV: &str = include_str!("../../data/cities.csv");
+
+static CITIES: OnceLock<Vec<City>> = OnceLock::new();
+
+#[derive(Debug)]
+pub struct City {
+    pub name: String,
+    pub region: String,
+    pub country: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+fn cities() -> &'static [City] {
+    CITIES.get_or_init(|| {
+        CITIES_CSV
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let mut fields = line.split(',');
+                Some(City {
+                    name: fields.next()?.to_string(),
+                    region: fields.next()?.to_string(),
+                    country: fields.next()?.to_string(),
+                    latitude: fields.next()?.parse().ok()?,
+                    longitude: fields.next()?.parse().ok()?,
+                })
+            })
+            .collect()
+    })
+}
+
+/// Looks up a city in the bundled dataset. The country is an ISO 3166-1 alpha-2
+/// code and, when given, disambiguates cities sharing a name.
+pub fn geocode(city: &str, country: Option<&str>) -> Option<&'static City> {
+    let city = city.trim();
+    cities().iter().find(|candidate| {
+        candidate.name.eq_ignore_ascii_case(city)
+            && country.is_none_or(|country| candidate.country.eq_ignore_ascii_case(country.trim()))
+    })
+}
+
+/// Best-effort geocoding of a free-text location such as "Tashkent, UZ" or
+/// "Berlin, Germany": the first comma-separated part is treated as the city.
+pub fn geocode_location(location: &str) -> Option<&'static City> {
+    let mut parts = location.split(',').map(str::trim);
+    let city = parts.next()?;
+    let country = parts.next_back().filter(|part| part.len() == 2);
+    geocode(city, country).or_else(|| geocode(city, None))
+}
@@ -0,0 +1,56 @@
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::env;
+
+/// Sends plain-text email over SMTP. Without `SMTP_HOST` configured, messages
+/// are not sent; only the recipient and subject are logged, since bodies carry
+/// links such as the job extension tokens.
+#[derive(Clone)]
+pub struct Mailer {
+    transport: Option<AsyncSmtpTransport<Tokio1Executor>>,
+    from: Mailbox,
+}
+
+impl Mailer {
+    pub fn from_env() -> Self {
+        let from = env::var("SMTP_FROM")
+            .unwrap_or_else(|_| "Job Board <no-reply@localhost>".to_string())
+            .parse()
+            .expect("SMTP_FROM must be a valid mailbox");
+
+        let transport = env::var("SMTP_HOST").ok().map(|host| {
+            let mut builder = AsyncSmtpTransport::<Tokio1Executor>::relay(&host)
+                .expect("SMTP_HOST must be a valid host");
+            if let (Ok(username), Ok(password)) =
+                (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD"))
+            {
+                builder = builder.credentials(Credentials::new(username, password));
+            }
+            builder.build()
+        });
+
+        Mailer { transport, from }
+    }
+
+    pub async fn send(&self, to: &str, subject: &str, body: String) -> Result<(), String> {
+        let Some(transport) = &self.transport else {
+            eprintln!("SMTP_HOST not set, not sending \"{subject}\" to {to}");
+            return Ok(());
+        };
+
+        let to: Mailbox = to.parse().map_err(|error| format!("{error}"))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .body(body)
+            .map_err(|error| format!("{error}"))?;
+
+        transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|error| format!("{error}"))
+    }
+}
@@ -0,0 +1,14 @@
+pub mod alerts;
+pub mod analytics;
+pub mod duplicates;
+pub mod featured;
+pub mod feeds;
+pub mod geocoding;
+pub mod mailer;
+pub mod moderation;
+pub mod recommendations;
+pub mod scheduler;
+pub mod similar;
+pub mod skills;
+pub mod templates;
+pub mod translations;
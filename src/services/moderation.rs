@@ -0,0 +1,84 @@
+use std::env;
+
+/// Phrases that usually mean the employer wants to be paid, or paid outside
+/// the platform, which legitimate postings never ask for.
+const DEFAULT_PAYMENT_PHRASES: &[&str] = &[
+    "western union",
+    "moneygram",
+    "gift card",
+    "bitcoin",
+    "crypto wallet",
+    "wire transfer",
+    "registration fee",
+    "training fee",
+    "starter kit fee",
+    "pay upfront",
+];
+
+/// Link shorteners and messenger links that hide where applicants are sent.
+const DEFAULT_SUSPICIOUS_DOMAINS: &[&str] = &["bit.ly", "tinyurl.com", "t.me", "wa.me"];
+
+/// A comma-separated, case-insensitive list from the environment, or the
+/// defaults when the variable is not set.
+fn list_from_env(name: &str, defaults: &[&str]) -> Vec<String> {
+    match env::var(name) {
+        Ok(value) => value
+            .split(',')
+            .map(|item| item.trim().to_lowercase())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        Err(_) => defaults.iter().map(|item| item.to_string()).collect(),
+    }
+}
+
+/// The host of every `http(s)://` link in the text, lowercased.
+fn link_hosts(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .filter_map(|word| {
+            let start = word.find("http://").or_else(|| word.find("https://"))?;
+            let rest = word[start..].split_once("://")?.1;
+            let host = rest
+                .split(['/', '?', '#', ':'])
+                .next()?
+                .trim_end_matches(|character: char| !character.is_alphanumeric());
+            (!host.is_empty()).then(|| host.to_lowercase())
+        })
+        .collect()
+}
+
+/// Runs the automatic checks over a posting and returns why it looks
+/// suspicious; an empty list means it passed. Configured with
+/// `MODERATION_BANNED_KEYWORDS`, `MODERATION_PAYMENT_PHRASES` and
+/// `MODERATION_SUSPICIOUS_DOMAINS`, each a comma-separated list.
+pub fn check(title: &str, description: &str) -> Vec<String> {
+    let text = format!("{title}\n{description}").to_lowercase();
+    let mut flags = Vec::new();
+
+    for keyword in list_from_env("MODERATION_BANNED_KEYWORDS", &[]) {
+        if text.contains(&keyword) {
+            flags.push(format!("banned keyword: {keyword}"));
+        }
+    }
+
+    for phrase in list_from_env("MODERATION_PAYMENT_PHRASES", DEFAULT_PAYMENT_PHRASES) {
+        if text.contains(&phrase) {
+            flags.push(format!("off-platform payment: {phrase}"));
+        }
+    }
+
+    let domains = list_from_env("MODERATION_SUSPICIOUS_DOMAINS", DEFAULT_SUSPICIOUS_DOMAINS);
+    for host in link_hosts(&text) {
+        let suspicious = host.parse::<std::net::IpAddr>().is_ok()
+            || domains
+                .iter()
+                .any(|domain| host == *domain || host.ends_with(&format!(".{domain}")));
+        if suspicious {
+            let flag = format!("suspicious link: {host}");
+            if !flags.contains(&flag) {
+                flags.push(flag);
+            }
+        }
+    }
+
+    flags
+}
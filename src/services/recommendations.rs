@@ -0,0 +1,175 @@
+use crate::models::application::Application;
+use crate::models::job::{Job, JobSearch};
+use crate::models::saved_job::SavedJob;
+use crate::models::skill::{Skill, SkillOverlap};
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::{Error, PgPool};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+/// How much each signal adds to a job's score. Skills count by the share of
+/// the job's skills the seeker has, so a full match is worth `SKILLS_WEIGHT`.
+const APPLIED_CATEGORY_WEIGHT: f64 = 3.0;
+const APPLIED_LOCATION_WEIGHT: f64 = 2.0;
+const SAVED_CATEGORY_WEIGHT: f64 = 2.0;
+const SAVED_LOCATION_WEIGHT: f64 = 1.0;
+const SKILLS_WEIGHT: f64 = 4.0;
+/// Only the most recent saved jobs are used as signals.
+const MAX_SAVED_SIGNALS: i64 = 100;
+
+/// One signal behind a recommendation, with the points it added.
+#[derive(Debug, Serialize)]
+pub struct RecommendationReason {
+    /// `applied`, `saved` or `skills`.
+    pub kind: &'static str,
+    /// The applied or saved job the recommendation is based on.
+    pub job_id: Option<i32>,
+    /// What the two jobs have in common: `category` and/or `location`.
+    pub matched: Vec<&'static str>,
+    pub message: String,
+    pub points: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Recommendation {
+    pub job: Job,
+    pub score: f64,
+    pub reasons: Vec<RecommendationReason>,
+}
+
+fn same_location(a: &Job, b: &Job) -> bool {
+    match (&a.city, &b.city) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => a.location.trim().eq_ignore_ascii_case(b.location.trim()),
+    }
+}
+
+/// The job in `sources` that best explains recommending `job`: one sharing
+/// both category and location beats one sharing either, and earlier sources
+/// win ties, so callers pass the most recent first.
+fn best_source<'a>(
+    job: &Job,
+    sources: &'a [Job],
+    category_weight: f64,
+    location_weight: f64,
+) -> Option<(&'a Job, Vec<&'static str>, f64)> {
+    let mut best: Option<(&Job, Vec<&'static str>, f64)> = None;
+    for source in sources.iter().filter(|source| source.id != job.id) {
+        let mut matched = Vec::new();
+        let mut points = 0.0;
+        if source.category_id == job.category_id {
+            matched.push("category");
+            points += category_weight;
+        }
+        if same_location(source, job) {
+            matched.push("location");
+            points += location_weight;
+        }
+        if points > best.as_ref().map_or(0.0, |(_, _, best)| *best) {
+            best = Some((source, matched, points));
+        }
+    }
+    best
+}
+
+/// Ranks open jobs for a seeker by how they relate to the jobs the seeker
+/// applied to and saved and to their profile skills. Jobs already applied to
+/// and jobs with no signal at all are left out. Best matches come first,
+/// newest first among equals.
+pub async fn recommend(pool: &PgPool, user_id: &i32) -> Result<Vec<Recommendation>, Error> {
+    let mut applications = Application::find_by_user_id(pool, user_id).await?;
+    applications.sort_by_key(|application| Reverse(application.created_at));
+    let applied_ids: Vec<i32> = applications
+        .iter()
+        .map(|application| application.job_id)
+        .collect();
+    let mut applied_by_id: HashMap<i32, Job> = Job::find_by_ids(pool, &applied_ids)
+        .await?
+        .into_iter()
+        .map(|job| (job.id, job))
+        .collect();
+    let applied: Vec<Job> = applied_ids
+        .iter()
+        .filter_map(|job_id| applied_by_id.remove(job_id))
+        .collect();
+    let applied_ids: HashSet<i32> = applied_ids.into_iter().collect();
+
+    let saved = SavedJob::find_jobs_by_user_id(pool, user_id, MAX_SAVED_SIGNALS, 0).await?;
+
+    let mut overlaps: HashMap<i32, SkillOverlap> = Skill::overlap_for_user(pool, user_id)
+        .await?
+        .into_iter()
+        .map(|overlap| (overlap.job_id, overlap))
+        .collect();
+
+    let now = Utc::now().naive_utc();
+    let candidates = Job::search(pool, &JobSearch::default(), None, None).await?;
+
+    let mut recommendations: Vec<Recommendation> = candidates
+        .into_iter()
+        .filter(|job| !applied_ids.contains(&job.id))
+        .filter(|job| job.expires_at.is_none_or(|expires_at| expires_at > now))
+        .filter_map(|job| {
+            let mut reasons = Vec::new();
+
+            let signals = [
+                (
+                    "applied",
+                    "applied to",
+                    &applied,
+                    APPLIED_CATEGORY_WEIGHT,
+                    APPLIED_LOCATION_WEIGHT,
+                ),
+                (
+                    "saved",
+                    "saved",
+                    &saved,
+                    SAVED_CATEGORY_WEIGHT,
+                    SAVED_LOCATION_WEIGHT,
+                ),
+            ];
+            for (kind, verb, sources, category_weight, location_weight) in signals {
+                if let Some((source, matched, points)) =
+                    best_source(&job, sources, category_weight, location_weight)
+                {
+                    reasons.push(RecommendationReason {
+                        kind,
+                        job_id: Some(source.id),
+                        matched,
+                        message: format!("Because you {verb} \"{}\"", source.title),
+                        points,
+                    });
+                }
+            }
+
+            if let Some(overlap) = overlaps.remove(&job.id) {
+                reasons.push(RecommendationReason {
+                    kind: "skills",
+                    job_id: None,
+                    matched: Vec::new(),
+                    message: format!("Matches your skills: {}", overlap.matched_skills.join(", ")),
+                    points: overlap.score() * SKILLS_WEIGHT,
+                });
+            }
+
+            if reasons.is_empty() {
+                return None;
+            }
+            reasons.sort_by(|a, b| b.points.total_cmp(&a.points));
+            let score = reasons.iter().map(|reason| reason.points).sum();
+            Some(Recommendation {
+                job,
+                score,
+                reasons,
+            })
+        })
+        .collect();
+    recommendations.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.job.created_at.cmp(&a.job.created_at))
+    });
+
+    Ok(recommendations)
+}
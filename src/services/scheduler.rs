@@ -0,0 +1,266 @@
+use crate::models::application::Application;
+use crate::models::job::{EXTENSION_DAYS, Job};
+use crate::models::knockout_rule::KNOCKOUT_REASON;
+use crate::models::notification::Notification;
+use crate::models::pipeline_stage::REJECTED_STAGE;
+use crate::models::user::User;
+use crate::services::alerts;
+use crate::services::feeds::app_url;
+use crate::services::mailer::Mailer;
+use crate::services::moderation;
+use actix_web::rt;
+use chrono::{Duration as ChronoDuration, Utc};
+use sqlx::PgPool;
+use std::env;
+use std::future::Future;
+use std::time::Duration;
+
+const SAVED_JOB_REMINDER_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const SAVED_JOB_REMINDER_WINDOW_HOURS: i32 = 72;
+const JOB_ALERT_INTERVAL: Duration = Duration::from_secs(60);
+const AUTO_REJECT_INTERVAL: Duration = Duration::from_secs(60);
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const JOB_LIFECYCLE_INTERVAL: Duration = Duration::from_secs(60);
+/// Jobs published or closed per transaction.
+const JOB_LIFECYCLE_BATCH: i64 = 100;
+const JOB_EXPIRY_REMINDER_INTERVAL: Duration = Duration::from_secs(15 * 60);
+const JOB_EXPIRY_REMINDER_WINDOW_HOURS: i32 = 72;
+const DEFAULT_RETENTION_DAYS: i64 = 90;
+
+/// Starts the periodic background tasks. Every task is safe to run from
+/// several server instances at once.
+pub fn start(pool: PgPool, mailer: Mailer) {
+    spawn_every(
+        SAVED_JOB_REMINDER_INTERVAL,
+        pool.clone(),
+        remind_expiring_saved_jobs,
+    );
+    let alert_mailer = mailer.clone();
+    spawn_every(JOB_ALERT_INTERVAL, pool.clone(), move |pool| {
+        send_job_alerts(pool, alert_mailer.clone())
+    });
+    spawn_every(JOB_LIFECYCLE_INTERVAL, pool.clone(), publish_scheduled_jobs);
+    spawn_every(JOB_LIFECYCLE_INTERVAL, pool.clone(), close_expired_jobs);
+    spawn_every(JOB_EXPIRY_REMINDER_INTERVAL, pool.clone(), move |pool| {
+        remind_expiring_jobs(pool, mailer.clone())
+    });
+    spawn_every(
+        AUTO_REJECT_INTERVAL,
+        pool.clone(),
+        reject_knocked_out_applications,
+    );
+
+    // Soft-deleted jobs and users are kept this many days before purging.
+    let retention_days = env::var("SOFT_DELETE_RETENTION_DAYS")
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+    spawn_every(PURGE_INTERVAL, pool, move |pool| {
+        purge_soft_deleted(pool, retention_days)
+    });
+}
+
+fn spawn_every<F, Fut>(period: Duration, pool: PgPool, task: F)
+where
+    F: Fn(PgPool) -> Fut + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    rt::spawn(async move {
+        let mut ticker = tokio::time::interval(period);
+        loop {
+            ticker.tick().await;
+            task(pool.clone()).await;
+        }
+    });
+}
+
+async fn remind_expiring_saved_jobs(pool: PgPool) {
+    if let Err(error) =
+        Notification::notify_saved_jobs_expiring(&pool, SAVED_JOB_REMINDER_WINDOW_HOURS).await
+    {
+        eprintln!("Failed to send saved job expiry reminders: {error}");
+    }
+}
+
+async fn send_job_alerts(pool: PgPool, mailer: Mailer) {
+    if let Err(error) = alerts::send_due_digests(&pool, &mailer).await {
+        eprintln!("Failed to send job alerts: {error}");
+    }
+}
+
+/// Publishes drafts whose `publish_at` has passed. Each batch is locked with
+/// SKIP LOCKED, so instances running this at the same time share the work.
+async fn publish_scheduled_jobs(pool: PgPool) {
+    loop {
+        match publish_due_batch(&pool).await {
+            Ok(published) if published < JOB_LIFECYCLE_BATCH as usize => return,
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("Failed to publish scheduled jobs: {error}");
+                return;
+            }
+        }
+    }
+}
+
+async fn publish_due_batch(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let drafts = Job::claim_due_drafts(&mut tx, JOB_LIFECYCLE_BATCH).await?;
+    for draft in &drafts {
+        let flags = moderation::check(&draft.title, &draft.description);
+        Job::publish_in(&mut tx, &draft.id, &flags).await?;
+    }
+    tx.commit().await?;
+
+    Ok(drafts.len())
+}
+
+/// Closes open jobs past their expiry and tells the employer and everyone who
+/// saved them.
+async fn close_expired_jobs(pool: PgPool) {
+    loop {
+        let jobs = match Job::close_expired(&pool, JOB_LIFECYCLE_BATCH).await {
+            Ok(jobs) => jobs,
+            Err(error) => {
+                eprintln!("Failed to close expired jobs: {error}");
+                return;
+            }
+        };
+        for job in &jobs {
+            let message = format!(
+                "Your job \"{}\" has reached its expiry date and closed",
+                job.title
+            );
+            if let Err(error) = Notification::notify_job_closed(&pool, &job.id, &job.title).await {
+                eprintln!(
+                    "Failed to notify seekers of expired job {}: {error}",
+                    job.id
+                );
+            }
+            let Some(employer_id) = job.employer_id else {
+                continue;
+            };
+            if let Err(error) =
+                Notification::create(&pool, &employer_id, "job_expired", Some(job.id), &message)
+                    .await
+            {
+                eprintln!(
+                    "Failed to notify employer of expired job {}: {error}",
+                    job.id
+                );
+            }
+        }
+        if jobs.len() < JOB_LIFECYCLE_BATCH as usize {
+            return;
+        }
+    }
+}
+
+/// Warns employers once before their posting closes, with a link to a page
+/// that extends it without logging in.
+async fn remind_expiring_jobs(pool: PgPool, mailer: Mailer) {
+    let reminders = match Job::claim_expiry_reminders(&pool, JOB_EXPIRY_REMINDER_WINDOW_HOURS).await
+    {
+        Ok(reminders) => reminders,
+        Err(error) => {
+            eprintln!("Failed to find expiring jobs: {error}");
+            return;
+        }
+    };
+
+    for reminder in reminders {
+        let message = format!(
+            "Your job \"{}\" closes on {} UTC",
+            reminder.title,
+            reminder.expires_at.format("%Y-%m-%d %H:%M")
+        );
+
+        let employer = match User::find_by_id(&pool, &reminder.employer_id).await {
+            Ok(employer) => employer,
+            Err(error) => {
+                eprintln!(
+                    "Failed to find employer of job {}: {error}",
+                    reminder.job_id
+                );
+                release_expiry_reminder(&pool, &reminder.job_id).await;
+                continue;
+            }
+        };
+        let body = format!(
+            "{message}.\n\nKeep it open for another {EXTENSION_DAYS} days: {}/api/jobs/extend/{}\n",
+            app_url(),
+            reminder.extend_token
+        );
+        if let Err(error) = mailer
+            .send(&employer.email, "Your job posting is about to close", body)
+            .await
+        {
+            eprintln!(
+                "Failed to email expiry reminder for job {}: {error}",
+                reminder.job_id
+            );
+            release_expiry_reminder(&pool, &reminder.job_id).await;
+            continue;
+        }
+
+        // Only once the email went out, so a retried reminder is not
+        // announced twice in the app.
+        if let Err(error) = Notification::create(
+            &pool,
+            &reminder.employer_id,
+            "job_expiring",
+            Some(reminder.job_id),
+            &message,
+        )
+        .await
+        {
+            eprintln!(
+                "Failed to notify employer of expiring job {}: {error}",
+                reminder.job_id
+            );
+        }
+    }
+}
+
+async fn release_expiry_reminder(pool: &PgPool, job_id: &i32) {
+    if let Err(error) = Job::release_expiry_reminder(pool, job_id).await {
+        eprintln!("Failed to release expiry reminder for job {job_id}: {error}");
+    }
+}
+
+/// Rejects applications that failed a knock-out rule once their delay is up.
+/// Anything moved on to another stage in the meantime is left alone.
+async fn reject_knocked_out_applications(pool: PgPool) {
+    let applications = match Application::find_due_auto_rejections(&pool).await {
+        Ok(applications) => applications,
+        Err(error) => {
+            eprintln!("Failed to find applications due for rejection: {error}");
+            return;
+        }
+    };
+    for application in applications {
+        if let Err(error) = Application::update_status(
+            &pool,
+            &application.id,
+            &application.status,
+            REJECTED_STAGE,
+            None,
+            Some(KNOCKOUT_REASON),
+            None,
+        )
+        .await
+        {
+            eprintln!("Failed to reject application {}: {error}", application.id);
+        }
+    }
+}
+
+async fn purge_soft_deleted(pool: PgPool, retention_days: i64) {
+    let deleted_before = Utc::now().naive_utc() - ChronoDuration::days(retention_days);
+    if let Err(error) = Job::purge_deleted(&pool, deleted_before).await {
+        eprintln!("Failed to purge deleted jobs: {error}");
+    }
+    if let Err(error) = User::purge_deleted(&pool, deleted_before).await {
+        eprintln!("Failed to purge deleted users: {error}");
+    }
+}
@@ -0,0 +1,59 @@
+use crate::models::job::{Job, SimilarJobScore, SimilarityWeights};
+use sqlx::{Error, PgPool};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const WEIGHTS: SimilarityWeights = SimilarityWeights {
+    category: 3.0,
+    location: 2.0,
+    salary: 1.0,
+    title: 4.0,
+};
+/// Jobs further apart than this get no location points.
+const RADIUS_KM: f64 = 100.0;
+/// The cache is dropped wholesale once it holds this many lists.
+const MAX_ENTRIES: usize = 10_000;
+
+/// The job, whether its own company's jobs were left out, and the limit.
+type CacheKey = (i32, bool, i64);
+/// The job change counter the scores were computed at, and the scores.
+type CacheEntry = (i64, Vec<SimilarJobScore>);
+
+/// Remembers similar-job scores per job. Every entry is tagged with the job
+/// change counter it was computed at and is ignored once any job changes, so
+/// results never outlive an edit, a new posting or a closure.
+#[derive(Clone, Default)]
+pub struct SimilarJobsCache {
+    entries: Arc<Mutex<HashMap<CacheKey, CacheEntry>>>,
+}
+
+impl SimilarJobsCache {
+    pub async fn similar_jobs(
+        &self,
+        pool: &PgPool,
+        job_id: i32,
+        exclude_company: bool,
+        limit: i64,
+    ) -> Result<Vec<SimilarJobScore>, Error> {
+        let key = (job_id, exclude_company, limit);
+        // Read before scoring: a change committed in between tags fresh
+        // scores with an older generation, which only costs a recompute.
+        let generation = Job::change_generation(pool).await?;
+        if let Some((cached_at, scores)) = self.entries.lock().unwrap().get(&key)
+            && *cached_at == generation
+        {
+            return Ok(scores.clone());
+        }
+
+        let scores =
+            Job::find_similar(pool, &job_id, &WEIGHTS, RADIUS_KM, exclude_company, limit).await?;
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES {
+            entries.clear();
+        }
+        entries.insert(key, (generation, scores.clone()));
+
+        Ok(scores)
+    }
+}
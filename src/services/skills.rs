@@ -0,0 +1,24 @@
+use crate::models::skill::Skill;
+use sqlx::{Error, PgPool};
+
+const SKILLS_CSV: &str = include_str!("../../data/skills.csv");
+
+/// Upserts the bundled skills vocabulary and its aliases. Safe to run on every
+/// start: existing skills and aliases are left in place.
+pub async fn seed_skills(pool: &PgPool) -> Result<(), Error> {
+    for line in SKILLS_CSV.lines().skip(1) {
+        let Some((name, aliases)) = line.split_once(',') else {
+            continue;
+        };
+        let skill = Skill::upsert(pool, name.trim()).await?;
+        for alias in aliases
+            .split('|')
+            .map(str::trim)
+            .filter(|alias| !alias.is_empty())
+        {
+            Skill::add_alias(pool, &skill.id, alias).await?;
+        }
+    }
+
+    Ok(())
+}
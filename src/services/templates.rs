@@ -0,0 +1,65 @@
+use std::collections::{BTreeSet, HashMap};
+
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Splits `text` on `{{name}}` placeholders. Names are letters, digits and
+/// underscores, with surrounding spaces allowed.
+fn parse(text: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        segments.push(Segment::Text(&rest[..start]));
+        let Some(length) = rest[start + 2..].find("}}") else {
+            return Err("Unclosed placeholder; placeholders look like {{name}}".to_string());
+        };
+        let name = rest[start + 2..start + 2 + length].trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!(
+                "Invalid placeholder {{{{{name}}}}}; names may only use letters, digits and underscores"
+            ));
+        }
+        segments.push(Segment::Placeholder(name));
+        rest = &rest[start + 2 + length + 2..];
+    }
+    segments.push(Segment::Text(rest));
+    Ok(segments)
+}
+
+/// The distinct placeholder names used across `texts`, sorted.
+pub fn placeholders(texts: &[&str]) -> Result<Vec<String>, String> {
+    let mut names = BTreeSet::new();
+    for text in texts {
+        for segment in parse(text)? {
+            if let Segment::Placeholder(name) = segment {
+                names.insert(name.to_string());
+            }
+        }
+    }
+    Ok(names.into_iter().collect())
+}
+
+/// Fills in every placeholder from `values`. Fails with the names that have
+/// no value.
+pub fn render(text: &str, values: &HashMap<String, String>) -> Result<String, Vec<String>> {
+    let mut rendered = String::with_capacity(text.len());
+    let mut missing = Vec::new();
+    // Stored templates were validated on save.
+    for segment in parse(text).unwrap_or_else(|_| vec![Segment::Text(text)]) {
+        match segment {
+            Segment::Text(text) => rendered.push_str(text),
+            Segment::Placeholder(name) => match values.get(name) {
+                Some(value) => rendered.push_str(value),
+                None => missing.push(name.to_string()),
+            },
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(rendered)
+    } else {
+        Err(missing)
+    }
+}
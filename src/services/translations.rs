@@ -0,0 +1,57 @@
+use crate::models::job::Job;
+use crate::models::job_translation::JobTranslation;
+use crate::utils::locale::best_locale;
+use sqlx::{Error, PgPool};
+use std::collections::HashMap;
+
+/// Replaces each job's title and description with the translation that best
+/// fits `locales`, most preferred first. A job keeps its own text when it is
+/// already in a preferred language or has no fitting translation, which is
+/// also what happens when the client states no preference.
+pub async fn localize(pool: &PgPool, jobs: Vec<&mut Job>, locales: &[String]) -> Result<(), Error> {
+    if locales.is_empty() || jobs.is_empty() {
+        return Ok(());
+    }
+
+    let job_ids: Vec<i32> = jobs.iter().map(|job| job.id).collect();
+    let mut languages: Vec<String> = locales
+        .iter()
+        .map(|locale| locale.split('-').next().unwrap_or(locale).to_string())
+        .collect();
+    languages.sort();
+    languages.dedup();
+
+    let mut translations: HashMap<i32, Vec<JobTranslation>> = HashMap::new();
+    for translation in JobTranslation::find_for_jobs(pool, &job_ids, &languages).await? {
+        translations
+            .entry(translation.job_id)
+            .or_default()
+            .push(translation);
+    }
+
+    for job in jobs {
+        let Some(candidates) = translations.remove(&job.id) else {
+            continue;
+        };
+        let mut available: Vec<&str> = vec![job.language.as_str()];
+        available.extend(
+            candidates
+                .iter()
+                .map(|translation| translation.locale.as_str()),
+        );
+
+        let Some(best) = best_locale(locales, &available).map(str::to_string) else {
+            continue;
+        };
+        if let Some(translation) = candidates
+            .into_iter()
+            .find(|translation| translation.locale == best)
+        {
+            job.title = translation.title;
+            job.description = translation.description;
+            job.language = translation.locale;
+        }
+    }
+
+    Ok(())
+}
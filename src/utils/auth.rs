@@ -1,3 +1,6 @@
+use crate::models::application::Application;
+use crate::models::company::CompanyMember;
+use crate::models::job::Job;
 use crate::models::user::User;
 use crate::utils::jwt::validate_token;
 use actix_web::HttpResponse;
@@ -12,3 +15,40 @@ pub async fn get_user_from_token(pool: &PgPool, token: &str) -> Result<User, Htt
         Err(_) => Err(HttpResponse::NotFound().json("User not found")),
     }
 }
+
+pub async fn get_admin_from_token(pool: &PgPool, token: &str) -> Result<User, HttpResponse> {
+    let user = get_user_from_token(pool, token).await?;
+    if user.role != "admin" {
+        return Err(HttpResponse::Forbidden().json("Admin access required"));
+    }
+    Ok(user)
+}
+
+/// Any member of the company that owns the job may edit it.
+pub async fn can_edit_job(pool: &PgPool, user: &User, job: &Job) -> bool {
+    matches!(
+        CompanyMember::find_role(pool, &job.company_id, &user.id).await,
+        Ok(Some(_))
+    )
+}
+
+/// Applicants can see their own applications, the hiring company's members
+/// the ones to its jobs, and admins all of them.
+pub async fn can_view_application(pool: &PgPool, user: &User, application: &Application) -> bool {
+    if user.id == application.user_id || user.role == "admin" {
+        return true;
+    }
+    match Job::find_by_id(pool, &application.job_id).await {
+        Ok(job) => can_edit_job(pool, user, &job).await,
+        Err(_) => false,
+    }
+}
+
+/// Recruiters may delete their own postings; owners and admins may delete any
+/// of the company's postings.
+pub async fn can_delete_job(pool: &PgPool, user: &User, job: &Job) -> bool {
+    match CompanyMember::find_role(pool, &job.company_id, &user.id).await {
+        Ok(Some(role)) => role == "owner" || role == "admin" || Some(user.id) == job.employer_id,
+        _ => false,
+    }
+}
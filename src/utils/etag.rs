@@ -0,0 +1,57 @@
+use actix_web::{
+    HttpRequest, HttpResponse,
+    http::header::{IF_MATCH, IF_NONE_MATCH},
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Formats a row version as a strong entity tag, e.g. `"3"`.
+pub fn etag(version: i32) -> String {
+    format!("\"{version}\"")
+}
+
+/// Tags generated content by hashing it, for responses without a row version.
+pub fn content_etag(body: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// Whether `If-None-Match` names `tag`, i.e. the client's copy is current.
+pub fn if_none_match(req: &HttpRequest, tag: &str) -> bool {
+    let Some(header) = req.headers().get(IF_NONE_MATCH) else {
+        return false;
+    };
+
+    header
+        .to_str()
+        .unwrap_or("")
+        .split(',')
+        .map(|candidate| candidate.trim().trim_start_matches("W/"))
+        .any(|candidate| candidate == "*" || candidate == tag)
+}
+
+/// Reads the version the client expects from `If-Match`. `None` means the
+/// header is absent or `*`, so any version is accepted. Anything that is not
+/// a single tag produced by `etag` can never match and fails with 412.
+pub fn if_match_version(req: &HttpRequest) -> Result<Option<i32>, HttpResponse> {
+    let Some(header) = req.headers().get(IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let value = header.to_str().unwrap_or("").trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .and_then(|version| version.parse().ok())
+        .map(Some)
+        .ok_or_else(precondition_failed)
+}
+
+pub fn precondition_failed() -> HttpResponse {
+    HttpResponse::PreconditionFailed().json("The resource has been modified since it was fetched")
+}
@@ -0,0 +1,87 @@
+use actix_web::{HttpRequest, http::header::ACCEPT_LANGUAGE, web::Query};
+use serde::Deserialize;
+
+/// The language of postings that do not say otherwise.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+#[derive(Deserialize)]
+struct LangQuery {
+    lang: Option<String>,
+}
+
+/// Lowercases a language tag such as `pt_BR` to `pt-br`, keeping only a
+/// primary language and at most one subtag. Returns `None` for anything else.
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase().replace('_', "-");
+    let mut parts = tag.split('-');
+    let primary = parts.next()?;
+    if !(2..=3).contains(&primary.len()) || !primary.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    if let Some(subtag) = parts.next()
+        && (!(2..=8).contains(&subtag.len()) || !subtag.chars().all(|c| c.is_ascii_alphanumeric()))
+    {
+        return None;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(tag)
+}
+
+/// The locales the client asked for, most preferred first: the `lang` query
+/// parameter, then `Accept-Language` by quality. Wildcards and malformed
+/// tags are skipped.
+pub fn requested_locales(req: &HttpRequest) -> Vec<String> {
+    let mut locales = Vec::new();
+    if let Ok(query) = Query::<LangQuery>::from_query(req.query_string())
+        && let Some(lang) = query.lang.as_deref().and_then(normalize_locale)
+    {
+        locales.push(lang);
+    }
+
+    let header = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|header| header.to_str().ok())
+        .unwrap_or("");
+    let mut accepted: Vec<(String, f64)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = normalize_locale(parts.next()?)?;
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |quality| quality.trim().parse().ok())?;
+            (quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    accepted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    for (tag, _) in accepted {
+        if !locales.contains(&tag) {
+            locales.push(tag);
+        }
+    }
+    locales
+}
+
+fn primary(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+/// The first requested locale that one of `available` satisfies, exactly or
+/// failing that by primary language, so `de-at` is served `de`.
+pub fn best_locale<'a>(requested: &[String], available: &[&'a str]) -> Option<&'a str> {
+    requested.iter().find_map(|locale| {
+        available
+            .iter()
+            .find(|candidate| **candidate == locale)
+            .or_else(|| {
+                available
+                    .iter()
+                    .find(|candidate| primary(candidate) == primary(locale))
+            })
+            .copied()
+    })
+}
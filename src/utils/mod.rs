@@ -1,2 +1,6 @@
 pub mod auth;
+pub mod etag;
 pub mod jwt;
+pub mod locale;
+pub mod pagination;
+pub mod slug;
@@ -0,0 +1,18 @@
+use sqlx::{Error, PgPool, query_scalar};
+
+/// Lowercases `value`, strips accents and collapses every run of other
+/// characters into a single dash, e.g. "Café & Bar" becomes "cafe-bar".
+/// Done in SQL with `unaccent` so slugs match the ones the category backfill
+/// derived.
+pub async fn slugify(pool: &PgPool, value: &str) -> Result<String, Error> {
+    let slug = query_scalar!(
+        r#"
+            SELECT trim(BOTH '-' FROM regexp_replace(unaccent(lower($1)), '[^a-z0-9]+', '-', 'g')) AS "slug!"
+        "#,
+        value.trim()
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(slug)
+}